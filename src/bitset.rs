@@ -238,7 +238,7 @@ impl RadixBitSet {
     }
 
     #[inline]
-    pub fn iter(&self) -> RadixBitSetIter {
+    pub fn iter(&self) -> RadixBitSetIter<'_> {
         RadixBitSetIter(match &self.state {
             State::Init => RadixBitSetIterState::Init,
             State::OneLevel { start_idx, chunks } => RadixBitSetIterState::OneLevel {
@@ -333,7 +333,7 @@ enum RadixBitSetIterState<'a> {
 #[derive(Debug)]
 struct Chunks<const N: usize>([usize; N]);
 impl<const N: usize> Chunks<N> {
-    fn iter(&self) -> ChunksIter {
+    fn iter(&self) -> ChunksIter<'_> {
         let mut chunk_iter = self.0.iter().copied().enumerate();
         let (chunk_idx, chunk) = chunk_iter.next().unwrap();
         let bit_iter = IterBits(chunk);
@@ -0,0 +1,25 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Path of the puzzle input for `day` of `year`, relative to `input_dir`.
+pub fn input_path(input_dir: impl AsRef<Path>, year: u16, day: u8) -> PathBuf {
+    input_dir
+        .as_ref()
+        .join(year.to_string())
+        .join(format!("day{}.txt", day))
+}
+
+/// Read the puzzle input for `day` of `year` from `input/<year>/day<day>.txt`.
+pub fn load_input(year: u16, day: u8) -> io::Result<String> {
+    read_input(input_path("input", year, day))
+}
+
+/// Read a puzzle input from `path`, stripping trailing newlines the same way
+/// cargo-aoc does before handing the input to a solver.
+pub fn read_input(path: impl AsRef<Path>) -> io::Result<String> {
+    let mut input = fs::read_to_string(path)?;
+    input.truncate(input.trim_end_matches('\n').len());
+    Ok(input)
+}
@@ -0,0 +1,212 @@
+use std::{cell::OnceCell, collections::BTreeMap, ops::RangeInclusive};
+
+/// A set of integers stored as disjoint, non-adjacent inclusive intervals.
+/// Every interval inserted is also remembered as an assignment so that
+/// `overlapping` can report which of them touch a range.
+#[derive(Debug, Clone, Default)]
+pub struct IntervalSet {
+    merged: BTreeMap<u64, u64>, // start -> end
+    assignments: Vec<RangeInclusive<u64>>,
+    tree: OnceCell<IntervalTree>,
+}
+impl IntervalSet {
+    pub fn new() -> Self {
+        IntervalSet::default()
+    }
+
+    pub fn insert(&mut self, range: RangeInclusive<u64>) {
+        if range.is_empty() {
+            return;
+        }
+        self.assignments.push(range.clone());
+        self.tree = OnceCell::new();
+
+        let (mut start, mut end) = range.into_inner();
+        // Absorb a predecessor that overlaps or touches, then any successors.
+        if let Some((&prev_start, &prev_end)) = self.merged.range(..=start).next_back() {
+            if prev_end.saturating_add(1) >= start {
+                start = prev_start;
+                end = end.max(prev_end);
+            }
+        }
+        while let Some((&next_start, &next_end)) = self.merged.range(start..).next() {
+            if next_start > end.saturating_add(1) {
+                break;
+            }
+            self.merged.remove(&next_start);
+            end = end.max(next_end);
+        }
+        self.merged.insert(start, end);
+    }
+
+    fn covering(&self, x: u64) -> Option<(u64, u64)> {
+        let (&start, &end) = self.merged.range(..=x).next_back()?;
+        (end >= x).then_some((start, end))
+    }
+
+    pub fn contains(&self, x: u64) -> bool {
+        self.covering(x).is_some()
+    }
+
+    /// Whether every value in `range` is in the set.
+    pub fn contains_range(&self, range: &RangeInclusive<u64>) -> bool {
+        range.is_empty()
+            || self
+                .covering(*range.start())
+                .is_some_and(|(_, end)| end >= *range.end())
+    }
+
+    /// Whether any value in `range` is in the set.
+    pub fn overlaps(&self, range: &RangeInclusive<u64>) -> bool {
+        !range.is_empty()
+            && self
+                .merged
+                .range(..=*range.end())
+                .next_back()
+                .is_some_and(|(_, &end)| end >= *range.start())
+    }
+
+    /// Number of values in the set.
+    pub fn len(&self) -> u64 {
+        self.merged.iter().map(|(start, end)| end - start + 1).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.merged.is_empty()
+    }
+
+    /// The merged intervals, in order.
+    pub fn intervals(&self) -> impl Iterator<Item = RangeInclusive<u64>> + '_ {
+        self.merged.iter().map(|(&start, &end)| start..=end)
+    }
+
+    /// The uncovered stretches between the first and last value in the set.
+    pub fn gaps(&self) -> impl Iterator<Item = RangeInclusive<u64>> + '_ {
+        self.merged
+            .iter()
+            .zip(self.merged.iter().skip(1))
+            .map(|((_, &end), (&next_start, _))| end + 1..=next_start - 1)
+    }
+
+    /// Every inserted interval, in insertion order.
+    pub fn assignments(&self) -> &[RangeInclusive<u64>] {
+        &self.assignments
+    }
+
+    /// Indices into `assignments` of the inserted intervals that share a value
+    /// with `range`, in no particular order.
+    pub fn overlapping(&self, range: &RangeInclusive<u64>) -> Vec<usize> {
+        self.tree
+            .get_or_init(|| IntervalTree::new(&self.assignments))
+            .overlapping(range)
+    }
+}
+impl FromIterator<RangeInclusive<u64>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<u64>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+/// A static interval tree: the intervals sorted by start form an implicit
+/// balanced search tree, where each node also knows the largest end in its
+/// subtree.
+#[derive(Debug, Clone)]
+pub struct IntervalTree {
+    nodes: Vec<(u64, u64, usize)>, // start, end, index of the interval
+    max_end: Vec<u64>,
+}
+impl IntervalTree {
+    pub fn new(intervals: &[RangeInclusive<u64>]) -> Self {
+        let mut nodes: Vec<_> = intervals
+            .iter()
+            .enumerate()
+            .filter(|(_, range)| !range.is_empty())
+            .map(|(idx, range)| (*range.start(), *range.end(), idx))
+            .collect();
+        nodes.sort_unstable();
+        let mut tree = IntervalTree {
+            max_end: vec![0; nodes.len()],
+            nodes,
+        };
+        tree.build(0, tree.nodes.len());
+        tree
+    }
+
+    fn build(&mut self, lo: usize, hi: usize) -> u64 {
+        if lo >= hi {
+            return 0;
+        }
+        let mid = (lo + hi) / 2;
+        let max_end = self.nodes[mid]
+            .1
+            .max(self.build(lo, mid))
+            .max(self.build(mid + 1, hi));
+        self.max_end[mid] = max_end;
+        max_end
+    }
+
+    pub fn overlapping(&self, range: &RangeInclusive<u64>) -> Vec<usize> {
+        let mut found = Vec::new();
+        if !range.is_empty() {
+            self.query(0, self.nodes.len(), range, &mut found);
+        }
+        found
+    }
+
+    fn query(&self, lo: usize, hi: usize, range: &RangeInclusive<u64>, found: &mut Vec<usize>) {
+        if lo >= hi {
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        if self.max_end[mid] < *range.start() {
+            return;
+        }
+        self.query(lo, mid, range, found);
+        let (start, end, idx) = self.nodes[mid];
+        if start <= *range.end() {
+            if end >= *range.start() {
+                found.push(idx);
+            }
+            self.query(mid + 1, hi, range, found);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn range() -> impl Strategy<Value = RangeInclusive<u64>> {
+        (0u64..100, 0u64..20).prop_map(|(start, len)| start..=start + len)
+    }
+
+    proptest! {
+        #[test]
+        fn matches_brute_force(ranges in prop::collection::vec(range(), 0..30), query in range()) {
+            let set: IntervalSet = ranges.iter().cloned().collect();
+            let covered = |x: u64| ranges.iter().any(|range| range.contains(&x));
+
+            prop_assert_eq!(set.len(), (0..200).filter(|&x| covered(x)).count() as u64);
+            for x in 0..200 {
+                prop_assert_eq!(set.contains(x), covered(x));
+            }
+            prop_assert_eq!(set.contains_range(&query), query.clone().all(covered));
+            prop_assert_eq!(set.overlaps(&query), query.clone().any(covered));
+            for gap in set.gaps() {
+                prop_assert!(!gap.is_empty() && !gap.clone().any(covered));
+            }
+
+            let mut overlapping = set.overlapping(&query);
+            overlapping.sort_unstable();
+            let expected: Vec<_> = (0..ranges.len())
+                .filter(|&idx| ranges[idx].start() <= query.end() && query.start() <= ranges[idx].end())
+                .collect();
+            prop_assert_eq!(overlapping, expected);
+        }
+    }
+}
@@ -1,31 +1,19 @@
 pub mod bitset;
+pub mod input;
+pub mod interval;
+pub mod ocr;
+pub mod registry;
+#[cfg(feature = "store")]
+pub mod store;
+pub mod topk;
+pub mod validate;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
-pub mod day1;
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day13;
-pub mod day14;
-pub mod day15;
-pub mod day16;
-pub mod day17;
-pub mod day18;
-pub mod day19;
-pub mod day2;
-pub mod day20;
-pub mod day21;
-pub mod day22;
-pub mod day23;
-pub mod day24;
-pub mod day25;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day8;
-pub mod day9;
+pub mod y2022;
 /*
 */
 
+// cargo-aoc only supports a single year per crate. Other years are reachable
+// through `registry`.
 aoc_runner_derive::aoc_lib! { year = 2022 }
@@ -0,0 +1,230 @@
+use std::{
+    fs::File,
+    io::{self, BufReader},
+    path::Path,
+    process::ExitCode,
+    time::Instant,
+};
+
+use aoc_2022::{
+    input::read_input,
+    registry,
+    store::{InputStore, StoreError, DEFAULT_SET},
+    validate::validate,
+    y2022::day10,
+};
+
+const INPUT_DIR: &str = "input";
+
+fn usage() -> ExitCode {
+    eprintln!("usage: aoc run <year> <day> [part] [input_file | --set <name>]");
+    eprintln!("       aoc validate <year> <day> [input_file | --set <name>]");
+    eprintln!("       aoc debug 2022 10 [input_file | --set <name>] [--script <file>]");
+    eprintln!("       aoc inputs import <dir | tarball> [--set <name>]");
+    eprintln!("       aoc inputs list <year> <day>");
+    eprintln!("       aoc inputs track");
+    eprintln!("       aoc inputs verify");
+    ExitCode::FAILURE
+}
+
+enum InputSource<'a> {
+    File(&'a str),
+    Set(&'a str),
+}
+impl InputSource<'_> {
+    fn describe(&self, year: u16, day: u8) -> String {
+        match self {
+            InputSource::File(path) => path.to_string(),
+            InputSource::Set(set) => format!("{} day {} ({})", year, day, set),
+        }
+    }
+
+    fn load(&self, year: u16, day: u8) -> Option<String> {
+        let result = match self {
+            InputSource::File(path) => {
+                read_input(path).map_err(|err| StoreError::Io(path.into(), err))
+            }
+            InputSource::Set(set) => {
+                InputStore::open(INPUT_DIR).and_then(|store| store.load(year, day, set))
+            }
+        };
+        result.map_err(|err| eprintln!("{}", err)).ok()
+    }
+}
+
+fn run(year: u16, day: u8, parts: &[u8], source: InputSource) -> ExitCode {
+    let Some(input) = source.load(year, day) else {
+        return ExitCode::FAILURE;
+    };
+    for &part in parts {
+        let Some(solver) = registry::solver(year, day, part) else {
+            if parts.len() == 1 {
+                eprintln!("no solution for {} day {} part {}", year, day, part);
+                return ExitCode::FAILURE;
+            }
+            continue;
+        };
+        let start = Instant::now();
+        let answer = solver(&input);
+        println!(
+            "{} day {} part {}: {} ({:?})",
+            year,
+            day,
+            part,
+            answer,
+            start.elapsed()
+        );
+    }
+    ExitCode::SUCCESS
+}
+
+fn run_validate(year: u16, day: u8, source: InputSource) -> ExitCode {
+    let Some(input) = source.load(year, day) else {
+        return ExitCode::FAILURE;
+    };
+    let Some(violations) = validate(year, day, &input) else {
+        eprintln!("no validator for {} day {}", year, day);
+        return ExitCode::FAILURE;
+    };
+    for violation in &violations {
+        println!("{}:{}", source.describe(year, day), violation);
+    }
+    if violations.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn run_debug(source: InputSource, script: Option<&str>) -> ExitCode {
+    let Some(input) = source.load(2022, 10) else {
+        return ExitCode::FAILURE;
+    };
+    let program = match day10::parse_program(&input) {
+        Ok(program) => program,
+        Err(err) => {
+            eprintln!("{}: {}", source.describe(2022, 10), err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut debugger = day10::Debugger::new(&program, day10::Crt::new(40, 6));
+    let result = match script {
+        Some(path) => File::open(path)
+            .and_then(|file| debugger.run_script(BufReader::new(file), io::stdout().lock())),
+        None => debugger.run_script(io::stdin().lock(), io::stdout().lock()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_inputs(args: &[&str], set: &str) -> Result<ExitCode, StoreError> {
+    let mut store = InputStore::open(INPUT_DIR)?;
+    match args {
+        ["import", source] => {
+            let imported = if Path::new(source).is_dir() {
+                store.import_dir(source, set)?
+            } else {
+                store.import_tarball(source, set)?
+            };
+            store.save_manifest()?;
+            for (year, day) in imported {
+                println!("imported {} day {} ({})", year, day, set);
+            }
+        }
+        ["list", year, day] => {
+            let (Ok(year), Ok(day)) = (year.parse(), day.parse()) else {
+                return Ok(usage());
+            };
+            for set in store.sets(year, day)? {
+                println!("{}", set);
+            }
+        }
+        ["track"] => {
+            for path in store.track_untracked()? {
+                println!("tracking {}", path);
+            }
+            store.save_manifest()?;
+        }
+        ["verify"] => {
+            let errors = store.verify();
+            for err in &errors {
+                eprintln!("{}", err);
+            }
+            if !errors.is_empty() {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        _ => return Ok(usage()),
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Removes `name <value>` from `args`, failing if the value is missing.
+fn take_option(args: &mut Vec<String>, name: &str) -> Result<Option<String>, ()> {
+    match args.iter().position(|arg| arg == name) {
+        Some(idx) if idx + 1 < args.len() => {
+            let value = args.remove(idx + 1);
+            args.remove(idx);
+            Ok(Some(value))
+        }
+        Some(_) => Err(()),
+        None => Ok(None),
+    }
+}
+
+fn main() -> ExitCode {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let (Ok(set), Ok(script)) = (
+        take_option(&mut args, "--set"),
+        take_option(&mut args, "--script"),
+    ) else {
+        return usage();
+    };
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    if let ["inputs", rest @ ..] = args.as_slice() {
+        return run_inputs(rest, set.as_deref().unwrap_or(DEFAULT_SET)).unwrap_or_else(|err| {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        });
+    }
+
+    let (command, year, day, rest) = match args.as_slice() {
+        [command, year, day, rest @ ..] => (*command, year.parse::<u16>(), day.parse::<u8>(), rest),
+        _ => return usage(),
+    };
+    let (Ok(year), Ok(day)) = (year, day) else {
+        return usage();
+    };
+    // `run` takes an optional part before the optional input file, so a lone
+    // argument is the part only if it's a number.
+    let num_optional_args = if command == "run" { 1 } else { 0 };
+    let (rest, source) = match (rest.split_last(), set.as_deref()) {
+        (Some((path, rest)), None)
+            if rest.len() == num_optional_args || path.parse::<u8>().is_err() =>
+        {
+            (rest, InputSource::File(path))
+        }
+        (_, set) => (rest, InputSource::Set(set.unwrap_or(DEFAULT_SET))),
+    };
+    match (command, rest) {
+        ("run", []) => run(year, day, &[1, 2], source),
+        ("run", [part]) => {
+            let Ok(part) = part.parse::<u8>() else {
+                return usage();
+            };
+            run(year, day, &[part], source)
+        }
+        ("validate", []) => run_validate(year, day, source),
+        ("debug", []) if (year, day) == (2022, 10) => run_debug(source, script.as_deref()),
+        ("debug", []) => {
+            eprintln!("no debugger for {} day {}", year, day);
+            ExitCode::FAILURE
+        }
+        _ => usage(),
+    }
+}
@@ -0,0 +1,167 @@
+use std::fmt;
+
+pub const GLYPH_WIDTH: usize = 4;
+pub const GLYPH_HEIGHT: usize = 6;
+/// Glyphs sit in every fifth column, with a blank column after each.
+pub const GLYPH_STRIDE: usize = GLYPH_WIDTH + 1;
+
+/// The letters Advent of Code draws in its 4x6 font.
+const GLYPHS: &[(char, [&str; GLYPH_HEIGHT])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// A glyph as a bitmask, row-major with the top-left pixel in bit 0.
+fn glyph_bits(rows: &[&str; GLYPH_HEIGHT]) -> u32 {
+    rows.iter()
+        .flat_map(|row| row.bytes())
+        .enumerate()
+        .filter(|&(_, b)| b == b'#')
+        .fold(0, |bits, (idx, _)| bits | 1 << idx)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    WrongHeight {
+        height: usize,
+    },
+    /// `text` has a `?` for each glyph starting at one of `columns`.
+    Unrecognized {
+        columns: Vec<usize>,
+        text: String,
+    },
+}
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OcrError::WrongHeight { height } => {
+                write!(f, "glyphs are {} pixels high, not {}", GLYPH_HEIGHT, height)
+            }
+            OcrError::Unrecognized { columns, text } => {
+                write!(
+                    f,
+                    "unrecognised glyphs at columns {:?} in {:?}",
+                    columns, text
+                )
+            }
+        }
+    }
+}
+impl std::error::Error for OcrError {}
+
+/// Reads the letters in a `width`-wide framebuffer of lit pixels, one row
+/// after another. Blank glyphs read as spaces, and a glyph whose separator
+/// column is lit is unrecognised.
+pub fn decode(pixels: &[bool], width: usize) -> Result<String, OcrError> {
+    let height = pixels.len().checked_div(width).unwrap_or(0);
+    if height != GLYPH_HEIGHT || height * width != pixels.len() {
+        return Err(OcrError::WrongHeight { height });
+    }
+    let mut text = String::new();
+    let mut columns = Vec::new();
+    for col in (0..width).step_by(GLYPH_STRIDE) {
+        let mut bits = 0u32;
+        let mut lit_separator = false;
+        for row in 0..GLYPH_HEIGHT {
+            for dx in 0..GLYPH_STRIDE {
+                if col + dx >= width || !pixels[row * width + col + dx] {
+                    continue;
+                }
+                if dx == GLYPH_WIDTH {
+                    lit_separator = true;
+                } else {
+                    bits |= 1 << (row * GLYPH_WIDTH + dx);
+                }
+            }
+        }
+        let glyph = GLYPHS.iter().find(|(_, rows)| glyph_bits(rows) == bits);
+        let letter = match glyph {
+            Some(&(letter, _)) if !lit_separator => letter,
+            None if bits == 0 && !lit_separator => ' ',
+            _ => {
+                columns.push(col);
+                '?'
+            }
+        };
+        text.push(letter);
+    }
+    if columns.is_empty() {
+        Ok(text)
+    } else {
+        Err(OcrError::Unrecognized { columns, text })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draw(word: &str) -> (Vec<bool>, usize) {
+        let width = word.len() * GLYPH_STRIDE;
+        let mut pixels = vec![false; width * GLYPH_HEIGHT];
+        for (idx, letter) in word.chars().enumerate() {
+            let Some((_, rows)) = GLYPHS.iter().find(|(glyph, _)| *glyph == letter) else {
+                continue;
+            };
+            for (row, line) in rows.iter().enumerate() {
+                for (dx, b) in line.bytes().enumerate() {
+                    pixels[row * width + idx * GLYPH_STRIDE + dx] = b == b'#';
+                }
+            }
+        }
+        (pixels, width)
+    }
+
+    #[test]
+    fn round_trip() {
+        let word: String = GLYPHS.iter().map(|(letter, _)| letter).collect();
+        let (pixels, width) = draw(&word);
+        assert_eq!(decode(&pixels, width), Ok(word));
+        // The last glyph's separator column is optional.
+        let (pixels, width) = draw("HI ZO");
+        let trimmed: Vec<_> = pixels
+            .chunks(width)
+            .flat_map(|row| &row[..width - 1])
+            .copied()
+            .collect();
+        assert_eq!(decode(&trimmed, width - 1), Ok("HI ZO".to_string()));
+
+        let (mut pixels, width) = draw("ABC");
+        pixels[width + GLYPH_STRIDE + 1] = true;
+        assert_eq!(
+            decode(&pixels, width),
+            Err(OcrError::Unrecognized {
+                columns: vec![5],
+                text: "A?C".to_string()
+            })
+        );
+        let (mut pixels, width) = draw("AB");
+        pixels[3 * width + GLYPH_WIDTH] = true;
+        assert_eq!(
+            decode(&pixels, width),
+            Err(OcrError::Unrecognized {
+                columns: vec![0],
+                text: "?B".to_string()
+            })
+        );
+        assert_eq!(
+            decode(&pixels[width..], width),
+            Err(OcrError::WrongHeight { height: 5 })
+        );
+    }
+}
@@ -0,0 +1,68 @@
+/// A solver for one part of one day's puzzle. The answer is returned formatted
+/// as a string so that solvers with different return types can share a table.
+pub type Solver = fn(&str) -> String;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Solution {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub solver: Solver,
+}
+
+macro_rules! solutions {
+    ($($year:literal => $year_mod:ident {
+        $($day:literal => $day_mod:ident [$($part:literal => $part_fn:ident),+]),+ $(,)?
+    }),+ $(,)?) => {
+        &[$($($(Solution {
+            year: $year,
+            day: $day,
+            part: $part,
+            solver: |input| crate::$year_mod::$day_mod::$part_fn(input).to_string(),
+        },)+)+)+]
+    };
+}
+
+pub static SOLUTIONS: &[Solution] = solutions! {
+    2022 => y2022 {
+        1 => day1 [1 => part1, 2 => part2],
+        2 => day2 [1 => part1, 2 => part2],
+        3 => day3 [1 => part1, 2 => part2],
+        4 => day4 [1 => part1, 2 => part2],
+        5 => day5 [1 => part1, 2 => part2],
+        6 => day6 [1 => part1, 2 => part2],
+        7 => day7 [1 => part1, 2 => part2],
+        8 => day8 [1 => part1, 2 => part2],
+        9 => day9 [1 => part1, 2 => part2],
+        10 => day10 [1 => part1, 2 => part2],
+        11 => day11 [1 => part1, 2 => part2],
+        12 => day12 [1 => part1, 2 => part2],
+        13 => day13 [1 => part1, 2 => part2],
+        14 => day14 [1 => part1, 2 => part2],
+        15 => day15 [1 => part1, 2 => part2],
+        16 => day16 [1 => part1, 2 => part2],
+        17 => day17 [1 => part1, 2 => part2],
+        18 => day18 [1 => part1, 2 => part2],
+        19 => day19 [1 => part1, 2 => part2],
+        20 => day20 [1 => part1, 2 => part2],
+        21 => day21 [1 => part1, 2 => part2],
+        22 => day22 [1 => part1, 2 => part2],
+        23 => day23 [1 => part1, 2 => part2],
+        24 => day24 [1 => part1, 2 => part2],
+        25 => day25 [1 => part1],
+    },
+};
+
+/// Look up the solver for `part` of `day` of `year`.
+pub fn solver(year: u16, day: u8, part: u8) -> Option<Solver> {
+    SOLUTIONS
+        .iter()
+        .find(|solution| (solution.year, solution.day, solution.part) == (year, day, part))
+        .map(|solution| solution.solver)
+}
+
+/// Solve `part` of `day` of `year` for the given input. Returns `None` if
+/// there is no solver registered for that puzzle.
+pub fn solve(year: u16, day: u8, part: u8, input: &str) -> Option<String> {
+    solver(year, day, part).map(|solver| solver(input))
+}
@@ -0,0 +1,438 @@
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use sha2::{Digest, Sha256};
+
+/// The input set stored at `<root>/<year>/day<N>.txt`, where cargo-aoc expects
+/// to find it. Every other set is stored at `<root>/<year>/<set>/day<N>.txt`.
+pub const DEFAULT_SET: &str = "real";
+
+/// Name of the manifest within the store root. It uses the same format as
+/// `sha256sum`, so `sha256sum -c MANIFEST` also works from the store root.
+const MANIFEST_FILE: &str = "MANIFEST";
+
+#[derive(Debug)]
+pub enum StoreError {
+    Io(PathBuf, io::Error),
+    InvalidSetName(String),
+    InvalidManifest {
+        line: usize,
+    },
+    Missing {
+        year: u16,
+        day: u8,
+        set: String,
+    },
+    ChecksumMismatch {
+        path: PathBuf,
+        expected: String,
+        actual: String,
+    },
+}
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreError::Io(path, err) => write!(f, "{}: {}", path.display(), err),
+            StoreError::InvalidSetName(set) => write!(f, "invalid input set name {:?}", set),
+            StoreError::InvalidManifest { line } => {
+                write!(
+                    f,
+                    "{} line {}: expected `<sha256>  <path>`",
+                    MANIFEST_FILE, line
+                )
+            }
+            StoreError::Missing { year, day, set } => {
+                write!(f, "no {:?} input for {} day {}", set, year, day)
+            }
+            StoreError::ChecksumMismatch {
+                path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{}: sha256 is {}, manifest expects {}",
+                path.display(),
+                actual,
+                expected
+            ),
+        }
+    }
+}
+impl Error for StoreError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            StoreError::Io(_, err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+fn io_err(path: &Path) -> impl FnOnce(io::Error) -> StoreError + '_ {
+    move |err| StoreError::Io(path.to_owned(), err)
+}
+
+fn sha256_hex(contents: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(contents))
+}
+
+fn check_set_name(set: &str) -> Result<(), StoreError> {
+    let valid = !set.is_empty()
+        && set
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_');
+    if valid {
+        Ok(())
+    } else {
+        Err(StoreError::InvalidSetName(set.to_owned()))
+    }
+}
+
+/// Extract the year and day from a path ending in `<year>/day<N>.txt`.
+fn year_and_day(path: &Path) -> Option<(u16, u8)> {
+    let mut components = path.components().rev();
+    let file_name = components.next()?.as_os_str().to_str()?;
+    let day = file_name
+        .strip_prefix("day")?
+        .strip_suffix(".txt")?
+        .parse()
+        .ok()?;
+    let year = components.next()?.as_os_str().to_str()?.parse().ok()?;
+    Some((year, day))
+}
+
+/// A local store of puzzle inputs with named input sets per day (for example
+/// "example", "real" or "generated-large"), verified against a SHA-256
+/// manifest.
+#[derive(Debug)]
+pub struct InputStore {
+    root: PathBuf,
+    // Paths relative to root, using '/' as the separator, mapped to their sha256.
+    manifest: BTreeMap<String, String>,
+}
+impl InputStore {
+    /// Open the store rooted at `root`, reading its manifest if there is one.
+    pub fn open(root: impl Into<PathBuf>) -> Result<Self, StoreError> {
+        let root = root.into();
+        let manifest_path = root.join(MANIFEST_FILE);
+        let mut manifest = BTreeMap::new();
+        match fs::read_to_string(&manifest_path) {
+            Ok(contents) => {
+                for (line_idx, line) in contents.lines().enumerate() {
+                    let (hash, path) = line
+                        .split_once("  ")
+                        .ok_or(StoreError::InvalidManifest { line: line_idx + 1 })?;
+                    manifest.insert(path.to_owned(), hash.to_owned());
+                }
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(StoreError::Io(manifest_path, err)),
+        }
+        Ok(InputStore { root, manifest })
+    }
+
+    fn relative_path(year: u16, day: u8, set: &str) -> String {
+        if set == DEFAULT_SET {
+            format!("{}/day{}.txt", year, day)
+        } else {
+            format!("{}/{}/day{}.txt", year, set, day)
+        }
+    }
+
+    /// Path of the input for `day` of `year` in the named set.
+    pub fn path(&self, year: u16, day: u8, set: &str) -> PathBuf {
+        self.root.join(Self::relative_path(year, day, set))
+    }
+
+    /// Names of the input sets available for `day` of `year`.
+    pub fn sets(&self, year: u16, day: u8) -> Result<Vec<String>, StoreError> {
+        let mut sets = Vec::new();
+        if self.path(year, day, DEFAULT_SET).is_file() {
+            sets.push(DEFAULT_SET.to_owned());
+        }
+        let year_dir = self.root.join(year.to_string());
+        let entries = match fs::read_dir(&year_dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(sets),
+            Err(err) => return Err(StoreError::Io(year_dir, err)),
+        };
+        for entry in entries {
+            let entry = entry.map_err(io_err(&year_dir))?;
+            let Some(set) = entry.file_name().to_str().map(str::to_owned) else {
+                continue;
+            };
+            if check_set_name(&set).is_ok() && self.path(year, day, &set).is_file() {
+                sets.push(set);
+            }
+        }
+        sets.sort();
+        Ok(sets)
+    }
+
+    /// Read the input for `day` of `year` from the named set, stripping
+    /// trailing newlines like cargo-aoc does. The contents are checked against
+    /// the manifest if the manifest has an entry for them.
+    pub fn load(&self, year: u16, day: u8, set: &str) -> Result<String, StoreError> {
+        check_set_name(set)?;
+        let relative_path = Self::relative_path(year, day, set);
+        let path = self.root.join(&relative_path);
+        let contents = match fs::read(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return Err(StoreError::Missing {
+                    year,
+                    day,
+                    set: set.to_owned(),
+                })
+            }
+            Err(err) => return Err(StoreError::Io(path, err)),
+        };
+        if let Some(expected) = self.manifest.get(&relative_path) {
+            let actual = sha256_hex(&contents);
+            if actual != *expected {
+                return Err(StoreError::ChecksumMismatch {
+                    path,
+                    expected: expected.clone(),
+                    actual,
+                });
+            }
+        }
+        let mut input = String::from_utf8(contents)
+            .map_err(|err| StoreError::Io(path, io::Error::new(io::ErrorKind::InvalidData, err)))?;
+        input.truncate(input.trim_end_matches('\n').len());
+        Ok(input)
+    }
+
+    /// Write the input for `day` of `year` into the named set and record its
+    /// checksum. Call `save_manifest` to persist the checksum.
+    pub fn insert(
+        &mut self,
+        year: u16,
+        day: u8,
+        set: &str,
+        contents: &[u8],
+    ) -> Result<(), StoreError> {
+        check_set_name(set)?;
+        let relative_path = Self::relative_path(year, day, set);
+        let path = self.root.join(&relative_path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(io_err(parent))?;
+        }
+        fs::write(&path, contents).map_err(io_err(&path))?;
+        self.manifest.insert(relative_path, sha256_hex(contents));
+        Ok(())
+    }
+
+    /// Import every file laid out as `<year>/day<N>.txt` beneath `dir` into the
+    /// named set. Returns the (year, day) of each imported input.
+    pub fn import_dir(
+        &mut self,
+        dir: impl AsRef<Path>,
+        set: &str,
+    ) -> Result<Vec<(u16, u8)>, StoreError> {
+        let mut imported = Vec::new();
+        let mut pending = vec![dir.as_ref().to_owned()];
+        while let Some(dir) = pending.pop() {
+            for entry in fs::read_dir(&dir).map_err(io_err(&dir))? {
+                let path = entry.map_err(io_err(&dir))?.path();
+                if path.is_dir() {
+                    pending.push(path);
+                } else if let Some((year, day)) = year_and_day(&path) {
+                    let contents = fs::read(&path).map_err(io_err(&path))?;
+                    self.insert(year, day, set, &contents)?;
+                    imported.push((year, day));
+                }
+            }
+        }
+        imported.sort();
+        Ok(imported)
+    }
+
+    /// Import every entry laid out as `<year>/day<N>.txt` in a tarball into the
+    /// named set. Tarballs ending in `.gz` or `.tgz` are decompressed first.
+    /// Returns the (year, day) of each imported input.
+    pub fn import_tarball(
+        &mut self,
+        tarball: impl AsRef<Path>,
+        set: &str,
+    ) -> Result<Vec<(u16, u8)>, StoreError> {
+        let tarball = tarball.as_ref();
+        let file = fs::File::open(tarball).map_err(io_err(tarball))?;
+        let is_gzipped = matches!(
+            tarball.extension().and_then(|ext| ext.to_str()),
+            Some("gz" | "tgz")
+        );
+        let reader: Box<dyn Read> = if is_gzipped {
+            Box::new(flate2::read::GzDecoder::new(file))
+        } else {
+            Box::new(file)
+        };
+        let mut archive = tar::Archive::new(reader);
+        let mut imported = Vec::new();
+        for entry in archive.entries().map_err(io_err(tarball))? {
+            let mut entry = entry.map_err(io_err(tarball))?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let Some((year, day)) = entry.path().ok().and_then(|path| year_and_day(&path)) else {
+                continue;
+            };
+            let mut contents = Vec::new();
+            entry.read_to_end(&mut contents).map_err(io_err(tarball))?;
+            self.insert(year, day, set, &contents)?;
+            imported.push((year, day));
+        }
+        imported.sort();
+        Ok(imported)
+    }
+
+    /// Record checksums for any inputs in the store that are not yet in the
+    /// manifest. Returns the paths that were added.
+    pub fn track_untracked(&mut self) -> Result<Vec<String>, StoreError> {
+        let mut added = Vec::new();
+        let mut pending = vec![self.root.clone()];
+        while let Some(dir) = pending.pop() {
+            for entry in fs::read_dir(&dir).map_err(io_err(&dir))? {
+                let path = entry.map_err(io_err(&dir))?.path();
+                if path.is_dir() {
+                    pending.push(path);
+                    continue;
+                }
+                let Ok(relative_path) = path.strip_prefix(&self.root) else {
+                    continue;
+                };
+                let relative_path = relative_path
+                    .components()
+                    .filter_map(|c| c.as_os_str().to_str())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                if year_and_day(&path).is_none() || self.manifest.contains_key(&relative_path) {
+                    continue;
+                }
+                let contents = fs::read(&path).map_err(io_err(&path))?;
+                self.manifest
+                    .insert(relative_path.clone(), sha256_hex(&contents));
+                added.push(relative_path);
+            }
+        }
+        added.sort();
+        Ok(added)
+    }
+
+    /// Check every input listed in the manifest, returning each one that is
+    /// missing or whose contents no longer match.
+    pub fn verify(&self) -> Vec<StoreError> {
+        let mut errors = Vec::new();
+        for (relative_path, expected) in &self.manifest {
+            let path = self.root.join(relative_path);
+            match fs::read(&path) {
+                Ok(contents) => {
+                    let actual = sha256_hex(&contents);
+                    if actual != *expected {
+                        errors.push(StoreError::ChecksumMismatch {
+                            path,
+                            expected: expected.clone(),
+                            actual,
+                        });
+                    }
+                }
+                Err(err) => errors.push(StoreError::Io(path, err)),
+            }
+        }
+        errors
+    }
+
+    /// Write the manifest to `<root>/MANIFEST`.
+    pub fn save_manifest(&self) -> Result<(), StoreError> {
+        let contents: String = self
+            .manifest
+            .iter()
+            .map(|(path, hash)| format!("{}  {}\n", hash, path))
+            .collect();
+        let path = self.root.join(MANIFEST_FILE);
+        fs::write(&path, contents).map_err(io_err(&path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc_store_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn import_load_and_verify() {
+        let src = temp_dir("src");
+        fs::create_dir_all(src.join("2022")).unwrap();
+        fs::write(src.join("2022/day1.txt"), "1\n2\n\n3\n\n").unwrap();
+        fs::write(src.join("2022/notes.txt"), "ignored").unwrap();
+        let root = temp_dir("root");
+
+        let mut store = InputStore::open(&root).unwrap();
+        assert_eq!(store.import_dir(&src, "example").unwrap(), vec![(2022, 1)]);
+        store.insert(2022, 1, DEFAULT_SET, b"4\n").unwrap();
+        store.save_manifest().unwrap();
+
+        let store = InputStore::open(&root).unwrap();
+        assert_eq!(store.sets(2022, 1).unwrap(), vec!["example", "real"]);
+        assert_eq!(store.load(2022, 1, "example").unwrap(), "1\n2\n\n3");
+        assert_eq!(store.load(2022, 1, DEFAULT_SET).unwrap(), "4");
+        assert!(store.verify().is_empty());
+        assert!(matches!(
+            store.load(2022, 2, DEFAULT_SET),
+            Err(StoreError::Missing { .. })
+        ));
+        assert!(matches!(
+            store.load(2022, 1, "../x"),
+            Err(StoreError::InvalidSetName(_))
+        ));
+
+        fs::write(root.join("2022/example/day1.txt"), "tampered").unwrap();
+        assert!(matches!(
+            store.load(2022, 1, "example"),
+            Err(StoreError::ChecksumMismatch { .. })
+        ));
+        assert_eq!(store.verify().len(), 1);
+
+        fs::remove_dir_all(src).unwrap();
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn import_gzipped_tarball() {
+        let root = temp_dir("tarball");
+        let tarball = root.join("inputs.tar.gz");
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            fs::File::create(&tarball).unwrap(),
+            flate2::Compression::default(),
+        ));
+        for (path, contents) in [("inputs/2022/day6.txt", "abcd\n"), ("README", "hi")] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, contents.as_bytes())
+                .unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let mut store = InputStore::open(&root).unwrap();
+        assert_eq!(
+            store.import_tarball(&tarball, "generated-large").unwrap(),
+            vec![(2022, 6)]
+        );
+        assert_eq!(store.load(2022, 6, "generated-large").unwrap(), "abcd");
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,67 @@
+/// Keeps the `k` largest items pushed so far, largest first.
+#[derive(Debug, Clone)]
+pub struct TopK<T> {
+    k: usize,
+    items: Vec<T>, // sorted descending, len <= k
+}
+impl<T: Ord> TopK<T> {
+    pub fn new(k: usize) -> Self {
+        TopK {
+            k,
+            items: Vec::with_capacity(k),
+        }
+    }
+
+    pub fn push(&mut self, item: T) {
+        if self.items.len() == self.k && self.items.last().is_none_or(|min| item <= *min) {
+            return;
+        }
+        // Ties keep their arrival order.
+        let idx = self.items.partition_point(|x| *x >= item);
+        self.items.insert(idx, item);
+        self.items.truncate(self.k);
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.items
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.items
+    }
+}
+impl<T: Ord> Extend<T> for TopK<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+pub trait TopKExt: Iterator + Sized
+where
+    Self::Item: Ord,
+{
+    fn top_k(self, k: usize) -> TopK<Self::Item> {
+        let mut top = TopK::new(k);
+        top.extend(self);
+        top
+    }
+}
+impl<I: Iterator> TopKExt for I where I::Item: Ord {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn matches_sort(items: Vec<u8>, k in 0usize..10) {
+            let mut sorted = items.clone();
+            sorted.sort_unstable_by(|a, b| b.cmp(a));
+            sorted.truncate(k);
+            prop_assert_eq!(items.into_iter().top_k(k).into_vec(), sorted);
+        }
+    }
+}
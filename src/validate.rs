@@ -0,0 +1,51 @@
+use std::fmt;
+
+/// An assumption about the puzzle input that a solver relies on but that the
+/// input does not uphold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// 1-based line number within the input, if the violation is local to a line.
+    pub line: Option<usize>,
+    pub message: String,
+}
+impl Violation {
+    pub(crate) fn at_line(line_idx: usize, message: impl Into<String>) -> Self {
+        Violation {
+            line: Some(line_idx + 1),
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn global(message: impl Into<String>) -> Self {
+        Violation {
+            line: None,
+            message: message.into(),
+        }
+    }
+}
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Check the input for `day` of `year` against the assumptions its solver
+/// makes without running the solver. Returns `None` if there is no validator
+/// for that day.
+pub fn validate(year: u16, day: u8, input: &str) -> Option<Vec<Violation>> {
+    use crate::y2022;
+    Some(match (year, day) {
+        (2022, 1) => y2022::day1::validate(input),
+        (2022, 3) => y2022::day3::validate(input),
+        (2022, 7) => y2022::day7::validate(input),
+        (2022, 16) => y2022::day16::validate(input),
+        (2022, 18) => y2022::day18::validate(input),
+        (2022, 21) => y2022::day21::validate(input),
+        (2022, 22) => y2022::day22::validate(input),
+        (2022, 24) => y2022::day24::validate(input),
+        _ => return None,
+    })
+}
@@ -0,0 +1,16 @@
+use wasm_bindgen::prelude::*;
+
+use crate::registry;
+
+/// Solve `part` of `day` of the 2022 puzzles for the given puzzle input.
+///
+/// Throws a JS exception if there is no solution for that day and part.
+#[wasm_bindgen]
+pub fn solve(day: u8, part: u8, input: &str) -> String {
+    // cargo-aoc strips trailing newlines before handing the input to a solver
+    // and several solvers rely on that.
+    match registry::solve(2022, day, part, input.trim_end_matches('\n')) {
+        Some(answer) => answer,
+        None => wasm_bindgen::throw_str(&format!("no solution for day {} part {}", day, part)),
+    }
+}
@@ -0,0 +1,208 @@
+use std::{cmp::Reverse, fmt};
+
+use aoc_runner_derive::aoc;
+
+use crate::{topk::TopKExt, validate::Violation};
+
+/// One elf's inventory. `index` is the elf's 0-based position in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elf {
+    pub index: usize,
+    pub name: Option<String>,
+    pub items: Vec<usize>,
+}
+impl Elf {
+    pub fn total(&self) -> usize {
+        self.items.iter().sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InventoryError {
+    /// 1-based line number within the input.
+    pub line: usize,
+    pub text: String,
+}
+impl fmt::Display for InventoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}: malformed calorie count {:?}",
+            self.line, self.text
+        )
+    }
+}
+impl std::error::Error for InventoryError {}
+
+/// Parse a calorie log. Besides the puzzle format this accepts CRLF line
+/// endings, surrounding whitespace, runs of blank lines, `#` comment lines and
+/// an optional `<name>:` header line that starts a new, named elf. Blank lines
+/// between a header and its first item are skipped.
+pub fn parse_elves(input: &str) -> Result<Vec<Elf>, InventoryError> {
+    let mut elves = Vec::new();
+    let mut current: Option<Elf> = None;
+    let new_elf = |name| Elf {
+        index: 0,
+        name,
+        items: Vec::new(),
+    };
+    for (line_idx, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            if current.as_ref().is_some_and(|elf| !elf.items.is_empty()) {
+                elves.extend(current.take());
+            }
+        } else if line.starts_with('#') {
+            continue;
+        } else if let Some(name) = line.strip_suffix(':') {
+            elves.extend(current.take());
+            current = Some(new_elf(Some(name.trim_end().to_string())));
+        } else {
+            let calories = line.parse().map_err(|_| InventoryError {
+                line: line_idx + 1,
+                text: line.to_string(),
+            })?;
+            current
+                .get_or_insert_with(|| new_elf(None))
+                .items
+                .push(calories);
+        }
+    }
+    elves.extend(current);
+    for (index, elf) in elves.iter_mut().enumerate() {
+        elf.index = index;
+    }
+    Ok(elves)
+}
+
+pub(crate) fn validate(input: &str) -> Vec<Violation> {
+    match parse_elves(input) {
+        Ok(elves) if elves.is_empty() => vec![Violation::global("no elves")],
+        Ok(_) => Vec::new(),
+        Err(err) => vec![Violation::at_line(
+            err.line - 1,
+            format!("malformed calorie count {:?}", err.text),
+        )],
+    }
+}
+
+/// The `k` elves carrying the most calories, most first. Ties go to the
+/// earlier elf.
+pub fn top_elves(elves: &[Elf], k: usize) -> Vec<&Elf> {
+    elves
+        .iter()
+        .enumerate()
+        .map(|(pos, elf)| (elf.total(), Reverse(pos)))
+        .top_k(k)
+        .into_vec()
+        .into_iter()
+        .map(|(_, Reverse(pos))| &elves[pos])
+        .collect()
+}
+
+/// A table of the top `k` elves. `elf` is the 1-based position in the input
+/// and `name` is only filled in for elves with a header.
+pub fn ranked_report(elves: &[Elf], k: usize) -> String {
+    let mut report = String::from("rank  elf  calories  items  name\n");
+    for (rank, elf) in top_elves(elves, k).into_iter().enumerate() {
+        let line = format!(
+            "{:>4}  {:>3}  {:>8}  {:>5}  {}",
+            rank + 1,
+            elf.index + 1,
+            elf.total(),
+            elf.items.len(),
+            elf.name.as_deref().unwrap_or_default()
+        );
+        report.push_str(line.trim_end());
+        report.push('\n');
+    }
+    report
+}
+
+fn sum_top_k(input: &str, k: usize) -> usize {
+    parse_elves(input)
+        .unwrap()
+        .iter()
+        .map(Elf::total)
+        .top_k(k)
+        .as_slice()
+        .iter()
+        .sum()
+}
+
+#[aoc(day1, part1)]
+pub fn part1(input: &str) -> usize {
+    sum_top_k(input, 1)
+}
+
+#[aoc(day1, part2)]
+pub fn part2(input: &str) -> usize {
+    sum_top_k(input, 3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tolerant_parse() {
+        let input =
+            "# exported log\r\nAlice:\r\n 1000 \r\n2000\r\n\r\n\r\n3000\r\nBob:\r\n4000\r\n\r\n";
+        let elves = parse_elves(input).unwrap();
+        assert_eq!(
+            elves,
+            vec![
+                Elf {
+                    index: 0,
+                    name: Some("Alice".to_string()),
+                    items: vec![1000, 2000],
+                },
+                Elf {
+                    index: 1,
+                    name: None,
+                    items: vec![3000],
+                },
+                Elf {
+                    index: 2,
+                    name: Some("Bob".to_string()),
+                    items: vec![4000],
+                },
+            ]
+        );
+        assert_eq!(
+            parse_elves("1000\n\n20x0").unwrap_err(),
+            InventoryError {
+                line: 3,
+                text: "20x0".to_string(),
+            }
+        );
+
+        let elves = parse_elves("Alice:\n\n100\n200\n\n300").unwrap();
+        assert_eq!(elves.len(), 2);
+        assert_eq!(elves[0].name.as_deref(), Some("Alice"));
+        assert_eq!(elves[0].items, vec![100, 200]);
+
+        assert_eq!(validate("# nothing"), vec![Violation::global("no elves")]);
+        assert_eq!(
+            validate("100\n\nabc"),
+            vec![Violation::at_line(2, "malformed calorie count \"abc\"")]
+        );
+    }
+
+    #[test]
+    fn report() {
+        let elves = parse_elves("1000\n\nAlice:\n3000\n500\n\n4000\n\nBob:\n2000").unwrap();
+        let top: Vec<_> = top_elves(&elves, 2).iter().map(|elf| elf.index).collect();
+        assert_eq!(top, vec![2, 1]);
+        assert!(top_elves(&elves, 0).is_empty());
+        assert_eq!(top_elves(&elves, 10).len(), 4);
+        assert_eq!(
+            ranked_report(&elves, 3),
+            "rank  elf  calories  items  name
+   1    3      4000      1
+   2    2      3500      2  Alice
+   3    4      2000      1  Bob
+"
+        );
+    }
+}
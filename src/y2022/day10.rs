@@ -0,0 +1,689 @@
+use std::{
+    fmt::{self, Write},
+    io::{self, BufRead},
+    str::FromStr,
+};
+
+use aoc_runner_derive::aoc;
+
+use crate::ocr::{self, OcrError};
+
+pub const REGISTER_NAMES: [&str; 4] = ["x", "y", "z", "w"];
+pub const X: usize = 0;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Op {
+    Noop,
+    Add,
+    Set,
+    Jump,
+    JumpIfZero,
+    JumpIfNotZero,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Operand {
+    Reg,
+    Imm,
+    /// A register or an immediate.
+    Value,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Arg {
+    Reg(usize),
+    Imm(isize),
+}
+
+/// One row of the instruction set. `implied` is prepended to the parsed
+/// operands, which is how `addx` is just `add x`.
+#[derive(Debug)]
+pub struct OpSpec {
+    pub mnemonic: &'static str,
+    pub op: Op,
+    pub implied: Option<Arg>,
+    pub operands: &'static [Operand],
+    pub cycles: usize,
+}
+
+pub const ISA: &[OpSpec] = &[
+    OpSpec {
+        mnemonic: "noop",
+        op: Op::Noop,
+        implied: None,
+        operands: &[],
+        cycles: 1,
+    },
+    OpSpec {
+        mnemonic: "addx",
+        op: Op::Add,
+        implied: Some(Arg::Reg(X)),
+        operands: &[Operand::Imm],
+        cycles: 2,
+    },
+    OpSpec {
+        mnemonic: "add",
+        op: Op::Add,
+        implied: None,
+        operands: &[Operand::Reg, Operand::Value],
+        cycles: 2,
+    },
+    OpSpec {
+        mnemonic: "set",
+        op: Op::Set,
+        implied: None,
+        operands: &[Operand::Reg, Operand::Value],
+        cycles: 1,
+    },
+    OpSpec {
+        mnemonic: "jmp",
+        op: Op::Jump,
+        implied: None,
+        operands: &[Operand::Imm],
+        cycles: 1,
+    },
+    OpSpec {
+        mnemonic: "jz",
+        op: Op::JumpIfZero,
+        implied: None,
+        operands: &[Operand::Reg, Operand::Imm],
+        cycles: 2,
+    },
+    OpSpec {
+        mnemonic: "jnz",
+        op: Op::JumpIfNotZero,
+        implied: None,
+        operands: &[Operand::Reg, Operand::Imm],
+        cycles: 2,
+    },
+];
+
+#[derive(Debug, Copy, Clone)]
+pub struct Instruction {
+    pub spec: &'static OpSpec,
+    pub args: [Arg; 2], // unused trailing args are `Imm(0)`
+}
+impl FromStr for Instruction {
+    type Err = &'static str;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut words = input.split_whitespace();
+        let mnemonic = words.next().ok_or("empty instruction")?;
+        let spec = ISA
+            .iter()
+            .find(|spec| spec.mnemonic == mnemonic)
+            .ok_or("unknown mnemonic")?;
+        let mut args = [Arg::Imm(0); 2];
+        let mut num_args = 0;
+        if let Some(implied) = spec.implied {
+            args[0] = implied;
+            num_args = 1;
+        }
+        for &operand in spec.operands {
+            let word = words.next().ok_or("missing operand")?;
+            let reg = REGISTER_NAMES.iter().position(|&name| name == word);
+            args[num_args] = match (operand, reg) {
+                (Operand::Reg | Operand::Value, Some(reg)) => Arg::Reg(reg),
+                (Operand::Reg, None) => return Err("expected a register"),
+                (Operand::Imm | Operand::Value, _) => {
+                    Arg::Imm(word.parse().map_err(|_| "expected a number")?)
+                }
+            };
+            num_args += 1;
+        }
+        if words.next().is_some() {
+            return Err("too many operands");
+        }
+        Ok(Instruction { spec, args })
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.spec.mnemonic)?;
+        let first = self.spec.implied.is_some() as usize;
+        for arg in &self.args[first..first + self.spec.operands.len()] {
+            match *arg {
+                Arg::Reg(reg) => write!(f, " {}", REGISTER_NAMES[reg])?,
+                Arg::Imm(imm) => write!(f, " {}", imm)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn parse_program(input: &str) -> Result<Vec<Instruction>, &'static str> {
+    input.lines().map(str::parse).collect()
+}
+
+/// What the CPU looks like during a cycle, before the instruction that
+/// finishes in it takes effect.
+#[derive(Debug, Copy, Clone)]
+pub struct Cycle {
+    pub number: usize, // 1-based, like the puzzle
+    pub regs: [isize; REGISTER_NAMES.len()],
+}
+impl Cycle {
+    pub fn x(&self) -> isize {
+        self.regs[X]
+    }
+}
+
+/// Something that watches every cycle, such as a signal probe or a screen.
+pub trait CycleHook {
+    fn during(&mut self, cycle: &Cycle);
+}
+impl<A: CycleHook, B: CycleHook> CycleHook for (A, B) {
+    fn during(&mut self, cycle: &Cycle) {
+        self.0.during(cycle);
+        self.1.during(cycle);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Cpu<'a> {
+    program: &'a [Instruction],
+    regs: [isize; REGISTER_NAMES.len()],
+    pc: usize,
+    cycle: usize,
+    current_instruction: Option<Instruction>,
+    current_instruction_cycles_remaining: usize,
+}
+impl<'a> Cpu<'a> {
+    pub fn new(program: &'a [Instruction]) -> Self {
+        let mut regs = [0; REGISTER_NAMES.len()];
+        regs[X] = 1;
+        Cpu {
+            program,
+            regs,
+            pc: 0,
+            cycle: 0,
+            current_instruction: None,
+            current_instruction_cycles_remaining: 0,
+        }
+    }
+
+    pub fn regs(&self) -> &[isize; REGISTER_NAMES.len()] {
+        &self.regs
+    }
+
+    /// Index of the instruction in flight, or of the next one to fetch.
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    pub fn current_instruction(&self) -> Option<&Instruction> {
+        self.current_instruction.as_ref()
+    }
+
+    pub fn current_instruction_cycles_remaining(&self) -> usize {
+        self.current_instruction_cycles_remaining
+    }
+
+    /// Number of cycles run so far.
+    pub fn cycle(&self) -> usize {
+        self.cycle
+    }
+
+    fn value(&self, arg: Arg) -> isize {
+        match arg {
+            Arg::Reg(reg) => self.regs[reg],
+            Arg::Imm(imm) => imm,
+        }
+    }
+
+    fn execute(&mut self, instruction: Instruction) {
+        let [a, b] = instruction.args;
+        let reg = match a {
+            Arg::Reg(reg) => reg,
+            Arg::Imm(_) => 0,
+        };
+        let mut offset = 1;
+        match instruction.spec.op {
+            Op::Noop => {}
+            Op::Add => self.regs[reg] += self.value(b),
+            Op::Set => self.regs[reg] = self.value(b),
+            Op::Jump => offset = self.value(a),
+            Op::JumpIfZero if self.value(a) == 0 => offset = self.value(b),
+            Op::JumpIfNotZero if self.value(a) != 0 => offset = self.value(b),
+            Op::JumpIfZero | Op::JumpIfNotZero => {}
+        }
+        self.pc = self.pc.checked_add_signed(offset).unwrap_or(usize::MAX);
+    }
+
+    /// Runs one cycle. Returns `None` once the program counter has left the
+    /// program and the last instruction has taken effect.
+    pub fn step(&mut self) -> Option<Cycle> {
+        if self.current_instruction_cycles_remaining == 0 {
+            if let Some(instruction) = self.current_instruction.take() {
+                self.execute(instruction);
+            }
+            let instruction = *self.program.get(self.pc)?;
+            self.current_instruction = Some(instruction);
+            self.current_instruction_cycles_remaining = instruction.spec.cycles;
+        }
+        self.current_instruction_cycles_remaining -= 1;
+        self.cycle += 1;
+        Some(Cycle {
+            number: self.cycle,
+            regs: self.regs,
+        })
+    }
+
+    /// Steps, showing each cycle to `hook`, until `stop` returns true for a
+    /// cycle or the program halts. Returns the last cycle run.
+    pub fn run_until(
+        &mut self,
+        hook: &mut impl CycleHook,
+        mut stop: impl FnMut(&Cycle) -> bool,
+    ) -> Option<Cycle> {
+        let mut last = None;
+        while let Some(cycle) = self.step() {
+            hook.during(&cycle);
+            last = Some(cycle);
+            if stop(&cycle) {
+                break;
+            }
+        }
+        last
+    }
+}
+
+/// Sums `cycle * x` over cycles `first`, `first + period`, ...
+#[derive(Debug, Clone)]
+pub struct SignalStrength {
+    pub first: usize,
+    pub period: usize,
+    pub sum: isize,
+}
+impl CycleHook for SignalStrength {
+    fn during(&mut self, cycle: &Cycle) {
+        if cycle.number >= self.first && (cycle.number - self.first).is_multiple_of(self.period) {
+            self.sum += cycle.number as isize * cycle.x();
+        }
+    }
+}
+
+/// A framebuffer drawn one pixel per cycle, left to right and top to
+/// bottom. A pixel is lit when the 3-wide sprite centred on `x` covers the
+/// column being drawn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crt {
+    width: usize,
+    height: usize,
+    pixels: Vec<bool>,
+}
+impl Crt {
+    pub fn new(width: usize, height: usize) -> Self {
+        Crt {
+            width,
+            height,
+            pixels: vec![false; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_lit(&self, row: usize, col: usize) -> bool {
+        self.pixels[row * self.width + col]
+    }
+
+    /// Row and column drawn during `cycle`, wrapping after the last pixel.
+    pub fn position(&self, cycle: usize) -> (usize, usize) {
+        let idx = (cycle - 1) % self.pixels.len();
+        (idx / self.width, idx % self.width)
+    }
+
+    pub fn read(&self) -> Result<String, OcrError> {
+        ocr::decode(&self.pixels, self.width)
+    }
+}
+impl CycleHook for Crt {
+    fn during(&mut self, cycle: &Cycle) {
+        let (row, col) = self.position(cycle.number);
+        self.pixels[row * self.width + col] =
+            (cycle.x() - 1..=cycle.x() + 1).contains(&(col as isize));
+    }
+}
+impl fmt::Display for Crt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.pixels.chunks(self.width) {
+            for &lit in row {
+                f.write_char(if lit { '#' } else { '.' })?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// After the given cycle has run.
+    Cycle(usize),
+    /// When the instruction at the given index starts.
+    Instruction(usize),
+}
+
+/// Stops when `reg` changes, or only when it changes to `value`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Watchpoint {
+    pub reg: usize,
+    pub value: Option<isize>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Stop {
+    Stepped,
+    Halted,
+    Breakpoint(Breakpoint),
+    Watchpoint(Watchpoint),
+}
+impl fmt::Display for Stop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Stop::Stepped => f.write_str("stepped"),
+            Stop::Halted => f.write_str("halted"),
+            Stop::Breakpoint(Breakpoint::Cycle(cycle)) => {
+                write!(f, "breakpoint at cycle {}", cycle)
+            }
+            Stop::Breakpoint(Breakpoint::Instruction(idx)) => {
+                write!(f, "breakpoint at instruction {}", idx)
+            }
+            Stop::Watchpoint(Watchpoint { reg, value: None }) => {
+                write!(f, "watchpoint: {} changed", REGISTER_NAMES[reg])
+            }
+            Stop::Watchpoint(Watchpoint {
+                reg,
+                value: Some(value),
+            }) => write!(f, "watchpoint: {} = {}", REGISTER_NAMES[reg], value),
+        }
+    }
+}
+
+/// Runs a program cycle by cycle while drawing to a `Crt`, stopping at
+/// breakpoints and watchpoints.
+#[derive(Debug, Clone)]
+pub struct Debugger<'a> {
+    cpu: Cpu<'a>,
+    crt: Crt,
+    last: Option<Cycle>,
+    pub breakpoints: Vec<Breakpoint>,
+    pub watchpoints: Vec<Watchpoint>,
+}
+impl<'a> Debugger<'a> {
+    pub fn new(program: &'a [Instruction], crt: Crt) -> Self {
+        Debugger {
+            cpu: Cpu::new(program),
+            crt,
+            last: None,
+            breakpoints: Vec::new(),
+            watchpoints: Vec::new(),
+        }
+    }
+
+    pub fn cpu(&self) -> &Cpu<'a> {
+        &self.cpu
+    }
+
+    pub fn crt(&self) -> &Crt {
+        &self.crt
+    }
+
+    fn step_cycle(&mut self) -> Option<Stop> {
+        let Some(cycle) = self.cpu.step() else {
+            return Some(Stop::Halted);
+        };
+        self.crt.during(&cycle);
+        let before = self
+            .last
+            .replace(cycle)
+            .map_or(*self.cpu.regs(), |last| last.regs);
+        let started = self
+            .cpu
+            .current_instruction()
+            .is_some_and(|instruction| {
+                self.cpu.current_instruction_cycles_remaining() + 1 == instruction.spec.cycles
+            })
+            .then_some(self.cpu.pc());
+        let breakpoint = self
+            .breakpoints
+            .iter()
+            .find(|&&breakpoint| match breakpoint {
+                Breakpoint::Cycle(number) => number == cycle.number,
+                Breakpoint::Instruction(idx) => started == Some(idx),
+            });
+        if let Some(&breakpoint) = breakpoint {
+            return Some(Stop::Breakpoint(breakpoint));
+        }
+        self.watchpoints
+            .iter()
+            .find(|watch| {
+                before[watch.reg] != cycle.regs[watch.reg]
+                    && watch
+                        .value
+                        .is_none_or(|value| value == cycle.regs[watch.reg])
+            })
+            .map(|&watch| Stop::Watchpoint(watch))
+    }
+
+    /// Runs `num_cycles` cycles, ignoring breakpoints and watchpoints.
+    pub fn step(&mut self, num_cycles: usize) -> Stop {
+        for _ in 0..num_cycles {
+            if let Some(Stop::Halted) = self.step_cycle() {
+                return Stop::Halted;
+            }
+        }
+        Stop::Stepped
+    }
+
+    /// Runs until a breakpoint or watchpoint triggers or the program halts.
+    pub fn resume(&mut self) -> Stop {
+        loop {
+            if let Some(stop) = self.step_cycle() {
+                return stop;
+            }
+        }
+    }
+
+    /// The cycle just run, the instruction in flight and the registers.
+    pub fn status(&self) -> String {
+        let mut status = format!("cycle {}", self.cpu.cycle());
+        match self.cpu.current_instruction() {
+            Some(instruction) => write!(
+                status,
+                ", pc {}: {} ({} cycles remaining)",
+                self.cpu.pc(),
+                instruction,
+                self.cpu.current_instruction_cycles_remaining()
+            )
+            .unwrap(),
+            None => write!(status, ", pc {}", self.cpu.pc()).unwrap(),
+        }
+        for (name, value) in REGISTER_NAMES.iter().zip(self.cpu.regs()) {
+            write!(status, " {}={}", name, value).unwrap();
+        }
+        status
+    }
+
+    /// The CRT row being drawn, with the sprite under it and `^` under the
+    /// pixel drawn in the last cycle.
+    pub fn crt_row(&self) -> String {
+        let Some(cycle) = self.last else {
+            return "nothing drawn yet".to_string();
+        };
+        let (row, col) = self.crt.position(cycle.number);
+        let pixels: String = (0..=col)
+            .map(|c| if self.crt.is_lit(row, c) { '#' } else { '.' })
+            .collect();
+        let sprite: String = (0..self.crt.width() as isize)
+            .map(|c| {
+                if (cycle.x() - 1..=cycle.x() + 1).contains(&c) {
+                    '#'
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        format!("{}\n{}\n{:>3$}", pixels, sprite, "^", col + 1)
+    }
+
+    /// Runs one command and returns what it prints. Commands are `step [n]`,
+    /// `continue`, `break cycle <n>`, `break instr <idx>`, `watch <reg>
+    /// [value]`, `delete`, `status`, `crt` and `screen`.
+    pub fn command(&mut self, line: &str) -> Result<String, &'static str> {
+        let words: Vec<_> = line.split_whitespace().collect();
+        let number = |word: &str| word.parse().map_err(|_| "expected a number");
+        let output = match words.as_slice() {
+            ["step"] => format!("{}\n{}", self.step(1), self.status()),
+            ["step", n] => format!("{}\n{}", self.step(number(n)?), self.status()),
+            ["continue"] => format!("{}\n{}", self.resume(), self.status()),
+            ["break", "cycle", n] => {
+                self.breakpoints.push(Breakpoint::Cycle(number(n)?));
+                String::new()
+            }
+            ["break", "instr", n] => {
+                self.breakpoints.push(Breakpoint::Instruction(number(n)?));
+                String::new()
+            }
+            ["watch", reg, value @ ..] if value.len() <= 1 => {
+                let reg = REGISTER_NAMES
+                    .iter()
+                    .position(|name| name == reg)
+                    .ok_or("expected a register")?;
+                let value = match value {
+                    [value] => Some(value.parse().map_err(|_| "expected a number")?),
+                    _ => None,
+                };
+                self.watchpoints.push(Watchpoint { reg, value });
+                String::new()
+            }
+            ["delete"] => {
+                self.breakpoints.clear();
+                self.watchpoints.clear();
+                String::new()
+            }
+            ["status"] => self.status(),
+            ["crt"] => self.crt_row(),
+            ["screen"] => self.crt.to_string().trim_end().to_string(),
+            _ => return Err("unknown command"),
+        };
+        Ok(output)
+    }
+
+    /// Runs commands from `script` until it ends or says `quit`, writing their
+    /// output to `out`. Blank lines and lines starting with `#` are skipped.
+    pub fn run_script(&mut self, script: impl BufRead, mut out: impl io::Write) -> io::Result<()> {
+        for line in script.lines() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line == "quit" {
+                break;
+            }
+            match self.command(line) {
+                Ok(output) if output.is_empty() => {}
+                Ok(output) => writeln!(out, "{}", output)?,
+                Err(err) => writeln!(out, "error: {}", err)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[aoc(day10, part1)]
+pub fn part1(input: &str) -> isize {
+    let program = parse_program(input).unwrap();
+    let mut signal = SignalStrength {
+        first: 20,
+        period: 40,
+        sum: 0,
+    };
+    Cpu::new(&program).run_until(&mut signal, |cycle| cycle.number == 220);
+    signal.sum
+}
+
+#[aoc(day10, part2)]
+pub fn part2(input: &str) -> String {
+    let program = parse_program(input).unwrap();
+    let mut crt = Crt::new(40, 6);
+    Cpu::new(&program).run_until(&mut crt, |cycle| cycle.number == 240);
+    match crt.read() {
+        Ok(text) => text,
+        Err(err) => panic!("{err}\n{crt}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn isa() {
+        assert!("addx".parse::<Instruction>().is_err());
+        assert!("add 3 x".parse::<Instruction>().is_err());
+        assert!("nop".parse::<Instruction>().is_err());
+        assert!("".parse::<Instruction>().is_err());
+
+        // y = 5; do { x += 2; y -= 1 } while y != 0
+        let program = parse_program("set y 5\naddx 2\nadd y -1\njnz y -2\nnoop").unwrap();
+        let mut cpu = Cpu::new(&program);
+        let mut signal = SignalStrength {
+            first: 3,
+            period: 6,
+            sum: 0,
+        };
+        let last = cpu.run_until(&mut signal, |_| false).unwrap();
+        assert_eq!(last.number, 1 + 5 * 6 + 1);
+        assert_eq!(last.regs[..2], [11, 0]);
+        assert_eq!(signal.sum, 3 + 9 * 3 + 15 * 5 + 21 * 7 + 27 * 9);
+        assert!(cpu.step().is_none());
+    }
+
+    #[test]
+    fn debugger() {
+        let program = parse_program("noop\naddx 3\naddx -5\nset y 7\nnoop").unwrap();
+        let script = "
+            # stop when the second addx starts, then when x goes negative
+            break instr 2
+            watch x -1
+            continue
+            crt
+            continue
+            delete
+            watch y
+            continue
+            step 2
+            status
+            bogus
+        ";
+        let mut debugger = Debugger::new(&program, Crt::new(8, 1));
+        let mut out = Vec::new();
+        debugger.run_script(script.as_bytes(), &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "breakpoint at instruction 2
+cycle 4, pc 2: addx -5 (1 cycles remaining) x=4 y=0 z=0 w=0
+####
+...###..
+   ^
+watchpoint: x = -1
+cycle 6, pc 3: set y 7 (0 cycles remaining) x=-1 y=0 z=0 w=0
+watchpoint: y changed
+cycle 7, pc 4: noop (0 cycles remaining) x=-1 y=7 z=0 w=0
+halted
+cycle 7, pc 5 x=-1 y=7 z=0 w=0
+cycle 7, pc 5 x=-1 y=7 z=0 w=0
+error: unknown command
+"
+        );
+    }
+}
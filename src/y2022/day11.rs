@@ -4,7 +4,7 @@ use aoc_runner_derive::aoc;
 
 #[derive(Debug, Clone)]
 enum LitOrOld {
-    Lit(usize),
+    Lit(u64),
     Old,
 }
 #[derive(Debug, Clone)]
@@ -39,7 +39,7 @@ impl FromStr for Operation {
     }
 }
 impl Operation {
-    fn apply(&self, old_value: usize) -> usize {
+    fn apply(&self, old_value: u64) -> u64 {
         let op_value = match self.val {
             LitOrOld::Lit(v) => v,
             LitOrOld::Old => old_value,
@@ -55,11 +55,11 @@ impl Operation {
 
 #[derive(Debug)]
 struct Monkey {
-    items: Vec<usize>,
+    items: Vec<u64>,
     op: Operation,
-    test_divisor: usize,
+    test_divisor: u64,
     throw_dest: [usize; 2],
-    num_inspected_items: usize,
+    num_inspected_items: u64,
 }
 impl FromStr for Monkey {
     type Err = Infallible;
@@ -76,7 +76,7 @@ impl FromStr for Monkey {
             num_inspected_items: 0,
             items: items
                 .split(", ")
-                .map(|i| i.parse::<usize>().unwrap())
+                .map(|i| i.parse::<u64>().unwrap())
                 .collect(),
             op: op.parse().unwrap(),
             test_divisor: test.parse().unwrap(),
@@ -88,27 +88,27 @@ impl FromStr for Monkey {
     }
 }
 
-fn observe_round(monkeys: &mut [Monkey], modulus: usize, relief_level: usize) {
+fn observe_round(monkeys: &mut [Monkey], modulus: u64, relief_level: u64) {
     for i in 0..monkeys.len() {
         let monkey = &mut monkeys[i];
-        monkey.num_inspected_items += monkey.items.len();
+        monkey.num_inspected_items += monkey.items.len() as u64;
         let items = std::mem::take(&mut monkey.items);
         let op = monkey.op.clone();
         let test_divisor = monkey.test_divisor;
         let throw_dest = monkey.throw_dest;
         for worry_level in items {
             let worry_level = op.apply(worry_level) / relief_level % modulus;
-            monkeys[throw_dest[(worry_level % test_divisor == 0) as usize]]
+            monkeys[throw_dest[worry_level.is_multiple_of(test_divisor) as usize]]
                 .items
                 .push(worry_level);
         }
     }
 }
-fn monkey_business_level(monkeys: &mut [Monkey], num_rounds: usize, relief_level: usize) -> usize {
+fn monkey_business_level(monkeys: &mut [Monkey], num_rounds: usize, relief_level: u64) -> u64 {
     let modulus = monkeys
         .iter()
         .map(|monkey| monkey.test_divisor)
-        .product::<usize>();
+        .product::<u64>();
     for _ in 0..num_rounds {
         observe_round(monkeys, modulus, relief_level);
     }
@@ -118,11 +118,11 @@ fn monkey_business_level(monkeys: &mut [Monkey], num_rounds: usize, relief_level
         .rev()
         .take(2)
         .map(|monkey| monkey.num_inspected_items)
-        .product::<usize>()
+        .product::<u64>()
 }
 
 #[aoc(day11, part1)]
-pub fn part1(input: &str) -> usize {
+pub fn part1(input: &str) -> u64 {
     let mut monkeys: Vec<_> = input
         .split("\n\n")
         .map(|monkey_record| monkey_record.parse::<Monkey>().unwrap())
@@ -131,7 +131,7 @@ pub fn part1(input: &str) -> usize {
 }
 
 #[aoc(day11, part2)]
-pub fn part2(input: &str) -> usize {
+pub fn part2(input: &str) -> u64 {
     let mut monkeys: Vec<_> = input
         .split("\n\n")
         .map(|monkey_record| monkey_record.parse::<Monkey>().unwrap())
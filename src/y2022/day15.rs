@@ -54,9 +54,9 @@ struct Region {
     bottom_right: XY,
 }
 impl Region {
-    fn area(&self) -> usize {
-        (self.top_left.x.abs_diff(self.bottom_right.x) + 1)
-            * (self.top_left.y.abs_diff(self.bottom_right.y) + 1)
+    fn area(&self) -> u64 {
+        (self.top_left.x.abs_diff(self.bottom_right.x) + 1) as u64
+            * (self.top_left.y.abs_diff(self.bottom_right.y) + 1) as u64
     }
     fn corners(&self) -> impl Iterator<Item = XY> {
         [
@@ -259,7 +259,7 @@ fn divide_and_conquer(sensors: &[Sensor], region: Region) -> Option<XY> {
 }
 
 #[aoc(day15, part2)]
-pub fn part2(input: &str) -> isize {
+pub fn part2(input: &str) -> i64 {
     let bounds = XY {
         x: 4_000_000,
         y: 4_000_000,
@@ -276,7 +276,7 @@ pub fn part2(input: &str) -> isize {
         },
     )
     .unwrap();
-    x * 4_000_000 + y
+    x as i64 * 4_000_000 + y as i64
 }
 
 #[cfg(test)]
@@ -289,13 +289,53 @@ mod tests {
       fn test_quadrants(x in -100isize..100, y in -100isize..100, width in 2isize..100, height in 2isize..100) {
         let region = Region{
           top_left: XY{x, y},
-          bottom_right: XY{x: x+width as isize-1, y: y+height as isize-1},
+          bottom_right: XY{x: x+width-1, y: y+height-1},
         };
         let area = region.area();
         let quadrants: Vec<_> = region.quadrants().collect();
         dbg!(&region, &quadrants);
-        assert_eq!(quadrants.iter().map(|r| r.area()).sum::<usize>(), area);
+        assert_eq!(quadrants.iter().map(|r| r.area()).sum::<u64>(), area);
         assert!(quadrants.len() == 2 || quadrants.len() == 4);
       }
+
+      #[test]
+      fn divide_and_conquer_matches_cell_scan(
+        sensors in prop::collection::vec((-5isize..25, -5isize..25, -5isize..25, -5isize..25), 0..8),
+        width in 1isize..20,
+        height in 1isize..20,
+      ) {
+        let sensors: Vec<_> = sensors
+          .into_iter()
+          .map(|(sx, sy, bx, by)| Sensor {
+            sensor: XY { x: sx, y: sy },
+            beacon: XY { x: bx, y: by },
+          })
+          .collect();
+        let region = Region {
+          top_left: XY { x: 0, y: 0 },
+          bottom_right: XY { x: width - 1, y: height - 1 },
+        };
+        let uncovered = uncovered_cells(&sensors, &region);
+        match divide_and_conquer(&sensors, region) {
+          Some(xy) => prop_assert!(uncovered.contains(&xy), "{:?} is covered", xy),
+          None => prop_assert!(uncovered.is_empty(), "missed {:?}", uncovered),
+        }
+      }
+    }
+
+    // Oracle for divide_and_conquer: check every cell in the region against every sensor.
+    fn uncovered_cells(sensors: &[Sensor], region: &Region) -> Vec<XY> {
+        let mut uncovered = Vec::new();
+        for y in region.top_left.y..=region.bottom_right.y {
+            for x in region.top_left.x..=region.bottom_right.x {
+                let covered = sensors.iter().any(|sensor| {
+                    sensor.sensor.x.abs_diff(x) + sensor.sensor.y.abs_diff(y) <= sensor.mdist()
+                });
+                if !covered {
+                    uncovered.push(XY { x, y });
+                }
+            }
+        }
+        uncovered
     }
 }
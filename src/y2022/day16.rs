@@ -5,6 +5,8 @@ use std::{
 
 use aoc_runner_derive::aoc;
 
+use crate::validate::Violation;
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
 struct RoomId(u8);
 
@@ -131,6 +133,79 @@ fn parse_rooms(input: &str) -> (Box<[Room]>, RoomId) {
     (rooms.into(), id_to_idx["AA"])
 }
 
+pub(crate) fn validate(input: &str) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    let mut names = Vec::new();
+    let mut tunnels = Vec::new();
+    let mut num_valves = 0;
+    let mut has_zero_rate_room = false;
+    for (line_idx, line) in input.lines().enumerate() {
+        let parsed = line.strip_prefix("Valve ").and_then(|rem| {
+            let (name, rem) = rem.split_once(" has flow rate=")?;
+            let (rate, rem) = rem.split_once("; ")?;
+            let neighbors = rem
+                .strip_prefix("tunnels lead to valves ")
+                .or_else(|| rem.strip_prefix("tunnel leads to valve "))?;
+            Some((name, rate, neighbors))
+        });
+        let Some((name, rate, neighbors)) = parsed else {
+            violations.push(Violation::at_line(
+                line_idx,
+                "expected `Valve XX has flow rate=N; tunnels lead to valves YY, ZZ`",
+            ));
+            continue;
+        };
+        if name.len() != 2 {
+            violations.push(Violation::at_line(
+                line_idx,
+                format!("valve name {:?} is not 2 characters", name),
+            ));
+        }
+        match rate.parse::<usize>() {
+            Ok(0) => has_zero_rate_room = true,
+            Ok(_) => num_valves += 1,
+            Err(_) => violations.push(Violation::at_line(
+                line_idx,
+                format!("invalid flow rate {:?}", rate),
+            )),
+        }
+        names.push(name);
+        tunnels.extend(neighbors.split(", ").map(|neighbor| (line_idx, neighbor)));
+    }
+    for (line_idx, neighbor) in tunnels {
+        if neighbor.len() != 2 {
+            violations.push(Violation::at_line(
+                line_idx,
+                format!("tunnel destination {:?} is not 2 characters", neighbor),
+            ));
+        } else if !names.contains(&neighbor) {
+            violations.push(Violation::at_line(
+                line_idx,
+                format!("tunnel leads to unknown valve {:?}", neighbor),
+            ));
+        }
+    }
+    if names.len() > 64 {
+        violations.push(Violation::global(format!(
+            "{} rooms exceeds the maximum of 64",
+            names.len()
+        )));
+    }
+    if num_valves > 16 {
+        violations.push(Violation::global(format!(
+            "{} rooms with a non-zero flow rate exceeds the maximum of 16",
+            num_valves
+        )));
+    }
+    if !has_zero_rate_room {
+        violations.push(Violation::global("no room has a flow rate of 0"));
+    }
+    if !names.contains(&"AA") {
+        violations.push(Violation::global("no starting valve AA"));
+    }
+    violations
+}
+
 fn calculate_distances(rooms: &[Room]) -> Vec<Vec<u8>> {
     #[derive(Copy, Clone, Eq, PartialEq)]
     struct State {
@@ -323,3 +398,28 @@ pub fn part2(input: &str) -> usize {
         .max()
         .unwrap()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validation() {
+        let input = "Valve AA has flow rate=0; tunnels lead to valves BB, CC\n\
+                     Valve BB has flow rate=13; tunnel leads to valve AA";
+        assert_eq!(
+            validate(input),
+            vec![Violation::at_line(
+                0,
+                "tunnel leads to unknown valve \"CC\""
+            )]
+        );
+        assert_eq!(
+            validate("Valve BB has flow rate=13; tunnel leads to valve BB"),
+            vec![
+                Violation::global("no room has a flow rate of 0"),
+                Violation::global("no starting valve AA"),
+            ]
+        );
+    }
+}
@@ -279,13 +279,12 @@ struct RepeatData {
     end_of_repeat_rock_idx: usize,
     start_of_repeat_tallest_point: usize,
     end_of_repeat_tallest_point: usize,
-    height_deltas: u32,
     subsequent_height_increases: Vec<usize>,
 }
 fn find_repeating_loop(
     rock_stream: impl Iterator<Item = Rocks>,
-    mut jet_stream: impl Iterator<Item = char>,
-) -> RepeatData {
+    mut jet_stream: impl Iterator<Item = (usize, char)>,
+) -> Option<RepeatData> {
     let mut grid = HashSet::new();
     struct RepeatRecord {
         rock_idx: usize,
@@ -294,7 +293,8 @@ fn find_repeating_loop(
         seen_count: usize,
     }
     enum RepeatState {
-        Searching(HashMap<u32, RepeatRecord>),
+        // Keyed by the shape of the top of the tower and the index of the last jet.
+        Searching(HashMap<(u32, usize), RepeatRecord>),
         TrackingHeights(RepeatData),
     }
     let mut repeat_state = RepeatState::Searching(HashMap::new());
@@ -308,7 +308,8 @@ fn find_repeating_loop(
             y: tallest_point + 4,
         };
         loop {
-            match jet_stream.next().unwrap() {
+            let (jet_idx, jet) = jet_stream.next().unwrap();
+            match jet {
                 '>' if rock.can_move_right(pos, &grid) => pos.x += 1,
                 '<' if rock.can_move_left(pos, &grid) => pos.x -= 1,
                 _ => {}
@@ -321,12 +322,13 @@ fn find_repeating_loop(
                 repeat_state = match repeat_state {
                     RepeatState::Searching(mut tops) if rock == Rocks::Dash => {
                         if let Some(height_deltas) = top_height_delta(tallest_point, &grid) {
+                            let top_state = (height_deltas, jet_idx);
                             let new_state = if let Some(RepeatRecord {
                                 rock_idx: prior_rock_idx,
                                 tallest_point: prior_tallest_point,
                                 seen_count,
                                 size_between,
-                            }) = tops.get_mut(&height_deltas)
+                            }) = tops.get_mut(&top_state)
                             {
                                 let diff = rock_idx - *prior_rock_idx;
                                 let tmp_rock_idx = *prior_rock_idx;
@@ -345,7 +347,6 @@ fn find_repeating_loop(
                                                 end_of_repeat_rock_idx: rock_idx,
                                                 start_of_repeat_tallest_point: tmp_tallest_point,
                                                 end_of_repeat_tallest_point: tallest_point,
-                                                height_deltas,
                                                 subsequent_height_increases: Vec::new(),
                                             })
                                         } else {
@@ -358,7 +359,7 @@ fn find_repeating_loop(
                                 }
                             } else {
                                 tops.insert(
-                                    height_deltas,
+                                    top_state,
                                     RepeatRecord {
                                         rock_idx,
                                         tallest_point,
@@ -374,13 +375,17 @@ fn find_repeating_loop(
                         }
                     }
                     RepeatState::TrackingHeights(mut repeat_data) => {
-                        if Some(repeat_data.height_deltas) == top_height_delta(tallest_point, &grid)
-                        {
-                            return repeat_data;
-                        }
                         repeat_data
                             .subsequent_height_increases
                             .push(tallest_point - repeat_data.end_of_repeat_tallest_point);
+                        // Record the increase for every rock in the loop except the last, which
+                        // completes the loop.
+                        if repeat_data.subsequent_height_increases.len() + 1
+                            == repeat_data.end_of_repeat_rock_idx
+                                - repeat_data.start_of_repeat_rock_idx
+                        {
+                            return Some(repeat_data);
+                        }
                         RepeatState::TrackingHeights(repeat_data)
                     }
                     RepeatState::Searching(x) => RepeatState::Searching(x),
@@ -389,26 +394,115 @@ fn find_repeating_loop(
             }
         }
     }
-    unreachable!()
+    None
+}
+
+impl RepeatData {
+    fn height_after(&self, num_rocks: u64) -> u64 {
+        let num_rocks_before_repeat_loop = self.start_of_repeat_rock_idx as u64 + 1;
+        let height_before_repeat_loop = self.start_of_repeat_tallest_point as u64;
+        let num_rocks_in_repeat_loop =
+            (self.end_of_repeat_rock_idx - self.start_of_repeat_rock_idx) as u64;
+        let height_of_repeat_loop =
+            (self.end_of_repeat_tallest_point - self.start_of_repeat_tallest_point) as u64;
+
+        let remaining = num_rocks - num_rocks_before_repeat_loop;
+        let loops = remaining / num_rocks_in_repeat_loop;
+        let remaining = (remaining % num_rocks_in_repeat_loop) as usize;
+        let partial_loop_height = match remaining {
+            0 => 0,
+            _ => self.subsequent_height_increases[remaining - 1] as u64,
+        };
+        height_before_repeat_loop + (height_of_repeat_loop * loops) + partial_loop_height
+    }
 }
 
 #[aoc(day17, part2)]
-pub fn part2(input: &str) -> usize {
-    let jet_stream = input.chars().cycle();
+pub fn part2(input: &str) -> u64 {
+    let jet_stream = input.chars().enumerate().cycle();
     let rock_stream = Rocks::iter();
-    let repeat_record = find_repeating_loop(rock_stream, jet_stream);
-    const NUM_ROCKS: usize = 1_000_000_000_000;
-    let num_rocks_before_repeat_loop = repeat_record.start_of_repeat_rock_idx + 1;
-    let height_before_repeat_loop = repeat_record.start_of_repeat_tallest_point;
-    let num_rocks_in_repeat_loop =
-        repeat_record.end_of_repeat_rock_idx - repeat_record.start_of_repeat_rock_idx;
-    let height_of_repeat_loop =
-        repeat_record.end_of_repeat_tallest_point - repeat_record.start_of_repeat_tallest_point;
+    let repeat_record = find_repeating_loop(rock_stream, jet_stream).unwrap();
+    repeat_record.height_after(1_000_000_000_000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
 
-    let remaining = NUM_ROCKS - num_rocks_before_repeat_loop;
-    let loops = remaining / num_rocks_in_repeat_loop;
-    let remaining = remaining % num_rocks_in_repeat_loop;
-    height_before_repeat_loop
-        + (height_of_repeat_loop * loops)
-        + repeat_record.subsequent_height_increases[remaining - 1]
+    // Oracle for find_repeating_loop: drop every rock without looking for a cycle.
+    fn simulate(jets: &str, num_rocks: usize) -> usize {
+        const SHAPES: [&[(usize, usize)]; 5] = [
+            &[(0, 0), (1, 0), (2, 0), (3, 0)],
+            &[(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)],
+            &[(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)],
+            &[(0, 0), (0, 1), (0, 2), (0, 3)],
+            &[(0, 0), (1, 0), (0, 1), (1, 1)],
+        ];
+        fn fits(shape: &[(usize, usize)], x: isize, y: usize, filled: &HashSet<XY>) -> bool {
+            shape.iter().all(|&(dx, dy)| {
+                let x = x + dx as isize;
+                (0..7).contains(&x)
+                    && y + dy > 0
+                    && !filled.contains(&XY {
+                        x: x as usize,
+                        y: y + dy,
+                    })
+            })
+        }
+        let mut filled = HashSet::new();
+        let mut jets = jets.bytes().cycle();
+        let mut height = 0;
+        for shape in SHAPES.iter().cycle().take(num_rocks) {
+            let (mut x, mut y) = (2isize, height + 4);
+            loop {
+                let pushed_x = if jets.next() == Some(b'<') {
+                    x - 1
+                } else {
+                    x + 1
+                };
+                if fits(shape, pushed_x, y, &filled) {
+                    x = pushed_x;
+                }
+                if fits(shape, x, y - 1, &filled) {
+                    y -= 1;
+                } else {
+                    for &(dx, dy) in shape.iter() {
+                        filled.insert(XY {
+                            x: x as usize + dx,
+                            y: y + dy,
+                        });
+                        height = height.max(y + dy);
+                    }
+                    break;
+                }
+            }
+        }
+        height
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+        #[test]
+        fn height_after_matches_simulation(jets in "[<>]{1,40}", extra_rocks in 0usize..2000) {
+            let Some(repeat_data) =
+                find_repeating_loop(Rocks::iter().take(20_000), jets.chars().enumerate().cycle())
+            else {
+                // Some jet patterns never fill every column, so no repeat can be found.
+                return Ok(());
+            };
+            let num_rocks = repeat_data.start_of_repeat_rock_idx + 1 + extra_rocks;
+            prop_assert_eq!(
+                repeat_data.height_after(num_rocks as u64),
+                simulate(&jets, num_rocks) as u64
+            );
+        }
+    }
+
+    #[test]
+    fn simulation_matches_example() {
+        let jets = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+        assert_eq!(simulate(jets, 2022), 3068);
+        assert_eq!(part1(jets), simulate(jets, 176));
+    }
 }
@@ -1,4 +1,4 @@
-use crate::bitset::RadixBitSet;
+use crate::{bitset::RadixBitSet, validate::Violation};
 
 use aoc_runner_derive::aoc;
 
@@ -52,6 +52,35 @@ fn neighbors_of(pos: Pos) -> impl Iterator<Item = Pos> {
     .into_iter()
 }
 
+pub(crate) fn validate(input: &str) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    for (line_idx, line) in input.lines().enumerate() {
+        let coords: Vec<_> = line.split(',').collect();
+        if coords.len() != 3 {
+            violations.push(Violation::at_line(
+                line_idx,
+                format!("expected 3 coordinates, found {}", coords.len()),
+            ));
+            continue;
+        }
+        for coord in coords {
+            // Neighbors are one either side, and `Pos` holds values below 20.
+            match coord.parse::<u16>() {
+                Ok(1..=18) => {}
+                Ok(v) => violations.push(Violation::at_line(
+                    line_idx,
+                    format!("coordinate {} is outside 1..=18", v),
+                )),
+                Err(_) => violations.push(Violation::at_line(
+                    line_idx,
+                    format!("invalid coordinate {:?}", coord),
+                )),
+            }
+        }
+    }
+    violations
+}
+
 #[aoc(day18, part1)]
 pub fn part1(input: &str) -> usize {
     let world = input
@@ -126,7 +155,7 @@ pub fn part2(input: &str) -> usize {
         });
     let mut visited = World::new();
     let mut cache = World::new();
-    let sum = world
+    world
         .iter()
         .map(|cube| {
             neighbors_of(cube)
@@ -138,8 +167,23 @@ pub fn part2(input: &str) -> usize {
                 })
                 .count()
         })
-        .sum();
-    dbg!(visited.0.space_used());
-    dbg!(cache.0.space_used());
-    sum
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validation() {
+        assert_eq!(validate("1,1,1\n18,2,3"), vec![]);
+        assert_eq!(
+            validate("1,1,1\n0,5,5\n19,5,5\n1,2"),
+            vec![
+                Violation::at_line(1, "coordinate 0 is outside 1..=18"),
+                Violation::at_line(2, "coordinate 19 is outside 1..=18"),
+                Violation::at_line(3, "expected 3 coordinates, found 2"),
+            ]
+        );
+    }
 }
@@ -70,10 +70,8 @@ fn max_geodes(blueprint: &Blueprint, minutes_remaining: u8) -> u16 {
                     } else if state.num_robots[mineral_type] == 0 {
                         minutes_remaining + 1
                     } else {
-                        ((costs[mineral_type] - state.minerals[mineral_type]
-                            + state.num_robots[mineral_type]
-                            - 1)
-                            / state.num_robots[mineral_type]) as u8
+                        (costs[mineral_type] - state.minerals[mineral_type])
+                            .div_ceil(state.num_robots[mineral_type]) as u8
                     }
                 })
                 .max()
@@ -85,10 +83,10 @@ fn max_geodes(blueprint: &Blueprint, minutes_remaining: u8) -> u16 {
 
             let mut state = state.clone();
 
-            for mineral_type in 0..4 {
-                state.minerals[mineral_type] += state.num_robots[mineral_type]
-                    * (minutes_required_to_build_robot as u16 + 1)
-                    - costs[mineral_type];
+            for (mineral_type, cost) in costs.iter().enumerate() {
+                state.minerals[mineral_type] = state.minerals[mineral_type]
+                    + state.num_robots[mineral_type] * (minutes_required_to_build_robot as u16 + 1)
+                    - cost;
             }
             state.num_robots[robot] += 1;
 
@@ -140,3 +138,96 @@ pub fn part2(input: &str) -> usize {
         .map(|blueprint| max_geodes(&blueprint, 32) as usize)
         .product()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::HashMap;
+
+    // Oracle for max_geodes: try every choice of robot to build (or none) every minute.
+    fn exhaustive_max_geodes(blueprint: &Blueprint, minutes_remaining: u8) -> u16 {
+        type Memo = HashMap<([u16; 4], [u16; 4], u8), u16>;
+        fn search(
+            blueprint: &Blueprint,
+            minerals: [u16; 4],
+            num_robots: [u16; 4],
+            minutes_remaining: u8,
+            memo: &mut Memo,
+        ) -> u16 {
+            if minutes_remaining == 0 {
+                return minerals[GEODE];
+            }
+            if let Some(&geodes) = memo.get(&(minerals, num_robots, minutes_remaining)) {
+                return geodes;
+            }
+            let mut collected = minerals;
+            for (mineral, robots) in collected.iter_mut().zip(num_robots) {
+                *mineral += robots;
+            }
+            let mut best = search(
+                blueprint,
+                collected,
+                num_robots,
+                minutes_remaining - 1,
+                memo,
+            );
+            for (robot, costs) in blueprint.costs.iter().enumerate() {
+                if costs.iter().zip(minerals).any(|(&cost, have)| cost > have) {
+                    continue;
+                }
+                let mut remaining = collected;
+                for (mineral, cost) in remaining.iter_mut().zip(costs) {
+                    *mineral -= cost;
+                }
+                let mut num_robots = num_robots;
+                num_robots[robot] += 1;
+                best = best.max(search(
+                    blueprint,
+                    remaining,
+                    num_robots,
+                    minutes_remaining - 1,
+                    memo,
+                ));
+            }
+            memo.insert((minerals, num_robots, minutes_remaining), best);
+            best
+        }
+        search(
+            blueprint,
+            [0, 0, 0, 0],
+            [1, 0, 0, 0],
+            minutes_remaining,
+            &mut HashMap::new(),
+        )
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+        #[test]
+        fn max_geodes_matches_exhaustive_search(
+            ore_costs in [1u16..5, 1..5, 1..5, 1..5],
+            obsidian_robot_clay_cost in 1u16..6,
+            geode_robot_obsidian_cost in 1u16..6,
+            minutes in 1u8..15,
+        ) {
+            let blueprint: Blueprint = format!(
+                "Blueprint 1: Each ore robot costs {} ore. Each clay robot costs {} ore. \
+                 Each obsidian robot costs {} ore and {} clay. \
+                 Each geode robot costs {} ore and {} obsidian.",
+                ore_costs[ORE],
+                ore_costs[CLAY],
+                ore_costs[OBSIDIAN],
+                obsidian_robot_clay_cost,
+                ore_costs[GEODE],
+                geode_robot_obsidian_cost,
+            )
+            .parse()
+            .unwrap();
+            prop_assert_eq!(
+                max_geodes(&blueprint, minutes),
+                exhaustive_max_geodes(&blueprint, minutes)
+            );
+        }
+    }
+}
@@ -0,0 +1,377 @@
+use std::str::FromStr;
+
+use aoc_runner_derive::aoc;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Choice {
+    Rock,
+    Paper,
+    Scissors,
+}
+impl Choice {
+    pub const ALL: [Choice; 3] = [Choice::Rock, Choice::Paper, Choice::Scissors];
+
+    /// Index of this choice in `RuleSet::classic`.
+    pub fn throw(self) -> usize {
+        self as usize
+    }
+}
+
+impl FromStr for Choice {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "A" | "X" => Ok(Choice::Rock),
+            "B" | "Y" => Ok(Choice::Paper),
+            "C" | "Z" => Ok(Choice::Scissors),
+            _ => Err("invalid input"),
+        }
+    }
+}
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GameResult {
+    Lose,
+    Draw,
+    Win,
+}
+impl FromStr for GameResult {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "X" => Ok(GameResult::Lose),
+            "Y" => Ok(GameResult::Draw),
+            "Z" => Ok(GameResult::Win),
+            _ => Err("invalid input"),
+        }
+    }
+}
+
+/// A rock-paper-scissors style game. Throws are identified by their index
+/// into `names`.
+#[derive(Debug, Clone)]
+pub struct RuleSet {
+    names: Vec<String>,
+    beats: Vec<u32>, // bit `b` of `beats[a]` is set if `a` beats `b`
+    shape_scores: Vec<usize>,
+    outcome_scores: [usize; 3], // indexed by `GameResult`
+}
+impl RuleSet {
+    pub fn new(
+        names: &[&str],
+        beats: &[(usize, usize)],
+        shape_scores: &[usize],
+        outcome_scores: [usize; 3],
+    ) -> Result<Self, &'static str> {
+        if names.len() > 32 {
+            return Err("too many throws");
+        }
+        if shape_scores.len() != names.len() {
+            return Err("need one shape score per throw");
+        }
+        let mut beats_mask = vec![0u32; names.len()];
+        for &(winner, loser) in beats {
+            if winner >= names.len() || loser >= names.len() {
+                return Err("unknown throw");
+            }
+            if winner == loser {
+                return Err("a throw cannot beat itself");
+            }
+            if beats_mask[loser] & (1 << winner) != 0 {
+                return Err("two throws cannot beat each other");
+            }
+            beats_mask[winner] |= 1 << loser;
+        }
+        Ok(RuleSet {
+            names: names.iter().map(|name| name.to_string()).collect(),
+            beats: beats_mask,
+            shape_scores: shape_scores.to_vec(),
+            outcome_scores,
+        })
+    }
+
+    pub fn classic() -> Self {
+        RuleSet::new(
+            &["Rock", "Paper", "Scissors"],
+            &[(0, 2), (1, 0), (2, 1)],
+            &[1, 2, 3],
+            [0, 3, 6],
+        )
+        .unwrap()
+    }
+
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        RuleSet::new(
+            &["Rock", "Paper", "Scissors", "Lizard", "Spock"],
+            &[
+                (2, 1), // scissors cuts paper
+                (1, 0), // paper covers rock
+                (0, 3), // rock crushes lizard
+                (3, 4), // lizard poisons Spock
+                (4, 2), // Spock smashes scissors
+                (2, 3), // scissors decapitates lizard
+                (3, 1), // lizard eats paper
+                (1, 4), // paper disproves Spock
+                (4, 0), // Spock vaporizes rock
+                (0, 2), // rock crushes scissors
+            ],
+            &[1, 2, 3, 4, 5],
+            [0, 3, 6],
+        )
+        .unwrap()
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn name(&self, throw: usize) -> &str {
+        &self.names[throw]
+    }
+
+    pub fn outcome(&self, opponent: usize, me: usize) -> GameResult {
+        if self.beats[me] & (1 << opponent) != 0 {
+            GameResult::Win
+        } else if self.beats[opponent] & (1 << me) != 0 {
+            GameResult::Lose
+        } else {
+            GameResult::Draw
+        }
+    }
+
+    pub fn score(&self, opponent: usize, me: usize) -> usize {
+        self.shape_scores[me] + self.outcome_scores[self.outcome(opponent, me) as usize]
+    }
+
+    /// The highest scoring throw that gets `result` against `opponent`.
+    pub fn response(&self, opponent: usize, result: GameResult) -> Option<usize> {
+        (0..self.len())
+            .filter(|&me| self.outcome(opponent, me) == result)
+            .max_by_key(|&me| self.shape_scores[me])
+    }
+
+    /// Opponent throws are written `A`, `B`, ... and ours end at `Z`, so the
+    /// classic game uses `X`, `Y`, `Z` and a five-throw game `V` to `Z`.
+    fn parse_throw(&self, s: &str, first: u8) -> Option<usize> {
+        match s.as_bytes() {
+            &[c] if c >= first && ((c - first) as usize) < self.len() => Some((c - first) as usize),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Strategy {
+    /// The second column is the throw to make.
+    Throw,
+    /// The second column is the result to aim for.
+    Outcome,
+}
+
+/// Total score from following `guide` under `rules`.
+pub fn play_guide(rules: &RuleSet, guide: &str, strategy: Strategy) -> Result<usize, &'static str> {
+    let my_first = (b'Z' + 1)
+        .checked_sub(rules.len() as u8)
+        .ok_or("too many throws")?;
+    guide
+        .lines()
+        .map(|game| {
+            let (opponent, second) = game.split_once(' ').ok_or("invalid input")?;
+            let opponent = rules.parse_throw(opponent, b'A').ok_or("invalid input")?;
+            let me = match strategy {
+                Strategy::Throw => rules.parse_throw(second, my_first),
+                Strategy::Outcome => rules.response(opponent, second.parse()?),
+            }
+            .ok_or("invalid input")?;
+            Ok(rules.score(opponent, me))
+        })
+        .sum()
+}
+
+/// One way of reading the guide's `X`, `Y` and `Z` as throws.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MappingScore {
+    pub mapping: [Choice; 3], // indexed by letter, `X` first
+    pub total: usize,
+}
+
+/// Reads a line of a classic guide as the opponent's throw and our letter.
+fn parse_game(game: &str) -> Result<(Choice, Choice), &'static str> {
+    let (opponent, letter) = game.split_once(' ').ok_or("invalid input")?;
+    Ok((opponent.parse()?, letter.parse()?))
+}
+
+/// The total score of `guide` under each of the six letter to `Choice`
+/// mappings.
+pub fn score_mappings(guide: &str) -> Result<Vec<MappingScore>, &'static str> {
+    // Count each (opponent, letter) pair once so each mapping is just 9 terms.
+    let mut counts = [[0; 3]; 3];
+    for game in guide.lines() {
+        let (opponent, letter) = parse_game(game)?;
+        counts[opponent.throw()][letter.throw()] += 1;
+    }
+    let rules = RuleSet::classic();
+    let [rock, paper, scissors] = Choice::ALL;
+    Ok([
+        [rock, paper, scissors],
+        [rock, scissors, paper],
+        [paper, rock, scissors],
+        [paper, scissors, rock],
+        [scissors, rock, paper],
+        [scissors, paper, rock],
+    ]
+    .into_iter()
+    .map(|mapping| MappingScore {
+        mapping,
+        total: (0..3)
+            .flat_map(|opponent| (0..3).map(move |letter| (opponent, letter)))
+            .map(|(opponent, letter)| {
+                counts[opponent][letter] * rules.score(opponent, mapping[letter].throw())
+            })
+            .sum(),
+    })
+    .collect())
+}
+
+/// The best and worst mapping for `guide`, in that order.
+pub fn optimize_mapping(guide: &str) -> Result<(MappingScore, MappingScore), &'static str> {
+    let scores = score_mappings(guide)?;
+    let best = *scores.iter().max_by_key(|score| score.total).unwrap();
+    let worst = *scores.iter().min_by_key(|score| score.total).unwrap();
+    Ok((best, worst))
+}
+
+/// How often the opponent makes each classic throw in `guide`.
+pub fn opponent_frequencies(guide: &str) -> Result<[f64; 3], &'static str> {
+    let mut counts = [0.0; 3];
+    for game in guide.lines() {
+        counts[parse_game(game)?.0.throw()] += 1.0;
+    }
+    Ok(counts)
+}
+
+/// Expected score of throwing `me` against an opponent who throws each throw
+/// in proportion to `weights`. The weights must have a positive sum.
+pub fn expected_score(rules: &RuleSet, weights: &[f64], me: usize) -> Result<f64, &'static str> {
+    let total: f64 = weights.iter().sum();
+    if total <= 0.0 {
+        return Err("weights must have a positive sum");
+    }
+    let weighted: f64 = weights
+        .iter()
+        .enumerate()
+        .map(|(opponent, weight)| weight * rules.score(opponent, me) as f64)
+        .sum();
+    Ok(weighted / total)
+}
+
+/// The throw with the highest expected score against `weights`.
+pub fn best_expected_response(
+    rules: &RuleSet,
+    weights: &[f64],
+) -> Result<(usize, f64), &'static str> {
+    let mut best = None;
+    for me in 0..rules.len() {
+        let expected = expected_score(rules, weights, me)?;
+        if best.is_none_or(|(_, best)| expected >= best) {
+            best = Some((me, expected));
+        }
+    }
+    best.ok_or("no throws")
+}
+
+#[aoc(day2, part1)]
+pub fn part1(input: &str) -> usize {
+    let rules = RuleSet::classic();
+    input
+        .lines()
+        .map(|game| {
+            (
+                game[..1].parse::<Choice>().unwrap(),
+                game[2..].parse::<Choice>().unwrap(),
+            )
+        })
+        .map(|(opponent, me)| rules.score(opponent.throw(), me.throw()))
+        .sum()
+}
+
+#[aoc(day2, part2)]
+pub fn part2(input: &str) -> usize {
+    let rules = RuleSet::classic();
+    input
+        .lines()
+        .map(|game| {
+            (
+                game[..1].parse::<Choice>().unwrap(),
+                game[2..].parse::<GameResult>().unwrap(),
+            )
+        })
+        .map(|(opponent, result)| {
+            let me = rules.response(opponent.throw(), result).unwrap();
+            rules.score(opponent.throw(), me)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classic_guides() {
+        let guide = "A Y\nB X\nC Z";
+        let rules = RuleSet::classic();
+        assert_eq!(play_guide(&rules, guide, Strategy::Throw), Ok(15));
+        assert_eq!(play_guide(&rules, guide, Strategy::Outcome), Ok(12));
+        assert_eq!(part1(guide), 15);
+        assert_eq!(part2(guide), 12);
+    }
+
+    #[test]
+    fn mappings() {
+        let guide = "A Y\nB X\nC Z";
+        let (best, worst) = optimize_mapping(guide).unwrap();
+        assert_eq!(score_mappings(guide).unwrap()[0].total, part1(guide));
+        assert_eq!(best.total, 24);
+        assert_eq!(
+            best.mapping,
+            [Choice::Scissors, Choice::Paper, Choice::Rock]
+        );
+        assert_eq!(worst.total, 6);
+
+        let rules = RuleSet::classic();
+        let freqs = opponent_frequencies(guide).unwrap();
+        assert_eq!(
+            expected_score(&rules, &freqs, Choice::Rock.throw()),
+            Ok(1.0 + 3.0)
+        );
+        assert_eq!(
+            best_expected_response(&rules, &[1.0, 0.0, 3.0]),
+            Ok((Choice::Rock.throw(), (4.0 + 3.0 * 7.0) / 4.0))
+        );
+        assert!(expected_score(&rules, &[0.0; 3], Choice::Rock.throw()).is_err());
+        assert!(best_expected_response(&rules, &[0.0; 3]).is_err());
+
+        assert_eq!(score_mappings("A"), Err("invalid input"));
+        assert_eq!(score_mappings("é Y"), Err("invalid input"));
+        assert_eq!(opponent_frequencies("A Y\nD X"), Err("invalid input"));
+    }
+
+    #[test]
+    fn rpsls_is_balanced() {
+        let rules = RuleSet::rock_paper_scissors_lizard_spock();
+        for throw in 0..rules.len() {
+            let wins = (0..rules.len())
+                .filter(|&other| rules.outcome(other, throw) == GameResult::Win)
+                .count();
+            assert_eq!(wins, 2, "{}", rules.name(throw));
+        }
+        // Spock vaporizes rock, scissors decapitates lizard.
+        assert_eq!(play_guide(&rules, "E V\nD X", Strategy::Throw), Ok(1 + 9));
+    }
+}
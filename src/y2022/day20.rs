@@ -1,21 +1,21 @@
 use aoc_runner_derive::aoc;
 
-fn wrap_idx(max_len: usize, value: isize) -> usize {
-    value.rem_euclid(max_len as isize) as usize
+fn wrap_idx(max_len: usize, value: i64) -> usize {
+    value.rem_euclid(max_len as i64) as usize
 }
 
-fn mix(input: &[isize], positions: &mut Vec<usize>) {
+fn mix(input: &[i64], positions: &mut Vec<usize>) {
     for (input_idx, value) in input.iter().copied().enumerate() {
         let pos = positions.iter().position(|&pos| pos == input_idx).unwrap();
         positions.remove(pos);
-        let new_pos = wrap_idx(positions.len(), pos as isize + value);
+        let new_pos = wrap_idx(positions.len(), pos as i64 + value);
         positions.insert(new_pos, input_idx);
     }
 }
 
 #[aoc(day20, part1)]
-pub fn part1(input: &str) -> isize {
-    let input: Vec<isize> = input.lines().map(|line| line.parse().unwrap()).collect();
+pub fn part1(input: &str) -> i64 {
+    let input: Vec<i64> = input.lines().map(|line| line.parse().unwrap()).collect();
     let mut positions: Vec<usize> = (0usize..input.len()).collect();
     mix(&input, &mut positions);
     let original_zero_idx = input.iter().copied().position(|value| value == 0).unwrap();
@@ -26,15 +26,15 @@ pub fn part1(input: &str) -> isize {
         .unwrap();
     [1000, 2000, 3000]
         .into_iter()
-        .map(|offset| input[positions[wrap_idx(input.len(), current_zero_idx as isize + offset)]])
+        .map(|offset| input[positions[wrap_idx(input.len(), current_zero_idx as i64 + offset)]])
         .sum()
 }
 
 #[aoc(day20, part2)]
-pub fn part2(input: &str) -> isize {
-    let input: Vec<isize> = input
+pub fn part2(input: &str) -> i64 {
+    let input: Vec<i64> = input
         .lines()
-        .map(|line| line.parse::<isize>().unwrap() * 811589153)
+        .map(|line| line.parse::<i64>().unwrap() * 811589153)
         .collect();
     let mut positions: Vec<usize> = (0usize..input.len()).collect();
     for _ in 0..10 {
@@ -48,6 +48,6 @@ pub fn part2(input: &str) -> isize {
         .unwrap();
     [1000, 2000, 3000]
         .into_iter()
-        .map(|offset| input[positions[wrap_idx(input.len(), current_zero_idx as isize + offset)]])
+        .map(|offset| input[positions[wrap_idx(input.len(), current_zero_idx as i64 + offset)]])
         .sum()
 }
@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+
+use aoc_runner_derive::aoc;
+
+use crate::validate::Violation;
+
+#[derive(Debug)]
+enum Job {
+    UnknownVariable,
+    Num(u64),
+    Add(String, String),
+    Sub(String, String),
+    Mul(String, String),
+    Div(String, String),
+}
+
+fn eval(monkeys: &HashMap<String, Job>, job: &Job) -> Option<u64> {
+    match job {
+        Job::UnknownVariable => None,
+        Job::Num(num) => Some(*num),
+        Job::Add(a, b) => {
+            let (a, b) = (monkeys.get(a.as_str())?, monkeys.get(b.as_str())?);
+            Some(eval(monkeys, a)? + eval(monkeys, b)?)
+        }
+        Job::Sub(a, b) => {
+            let (a, b) = (monkeys.get(a.as_str())?, monkeys.get(b.as_str())?);
+            Some(eval(monkeys, a)? - eval(monkeys, b)?)
+        }
+        Job::Mul(a, b) => {
+            let (a, b) = (monkeys.get(a.as_str())?, monkeys.get(b.as_str())?);
+            Some(eval(monkeys, a)? * eval(monkeys, b)?)
+        }
+        Job::Div(a, b) => {
+            let (a, b) = (monkeys.get(a.as_str())?, monkeys.get(b.as_str())?);
+            Some(eval(monkeys, a)? / eval(monkeys, b)?)
+        }
+    }
+}
+
+fn solve_equation(monkeys: &HashMap<String, Job>, lhs: &Job, rhs: &Job) -> u64 {
+    fn _known_unknown<'b>(
+        monkeys: &HashMap<String, Job>,
+        a: &'b Job,
+        b: &'b Job,
+    ) -> (u64, bool, &'b Job) {
+        match (eval(monkeys, a), eval(monkeys, b)) {
+            (Some(known), None) => (known, true, b),
+            (None, Some(known)) => (known, false, a),
+            (Some(a), Some(b)) => panic!("neither side is unknown: {:?} {:?}", a, b),
+            (None, None) => panic!("both sides have variables!"),
+        }
+    }
+    let (known, _, unknown) = _known_unknown(monkeys, lhs, rhs);
+    match unknown {
+        Job::UnknownVariable => known,
+        Job::Num(num) => *num,
+        Job::Add(a, b) => {
+            let (_known, _known_is_lhs, _unknown) =
+                _known_unknown(monkeys, &monkeys[a.as_str()], &monkeys[b.as_str()]);
+            solve_equation(monkeys, _unknown, &Job::Num(known - _known))
+        }
+        Job::Sub(a, b) => {
+            let (_known, _known_is_lhs, _unknown) =
+                _known_unknown(monkeys, &monkeys[a.as_str()], &monkeys[b.as_str()]);
+            if _known_is_lhs {
+                solve_equation(monkeys, _unknown, &Job::Num(_known - known))
+            } else {
+                solve_equation(monkeys, _unknown, &Job::Num(_known + known))
+            }
+        }
+        Job::Mul(a, b) => {
+            let (_known, _known_is_lhs, _unknown) =
+                _known_unknown(monkeys, &monkeys[a.as_str()], &monkeys[b.as_str()]);
+            solve_equation(monkeys, _unknown, &Job::Num(known / _known))
+        }
+        Job::Div(a, b) => {
+            let (_known, _known_is_lhs, _unknown) =
+                _known_unknown(monkeys, &monkeys[a.as_str()], &monkeys[b.as_str()]);
+            if _known_is_lhs {
+                solve_equation(monkeys, _unknown, &Job::Num(_known / known))
+            } else {
+                solve_equation(monkeys, _unknown, &Job::Num(_known * known))
+            }
+        }
+    }
+}
+
+pub(crate) fn validate(input: &str) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    let mut names = Vec::new();
+    let mut operands = Vec::new();
+    for (line_idx, line) in input.lines().enumerate() {
+        let Some((monkey, job)) = line.split_once(": ") else {
+            violations.push(Violation::at_line(line_idx, "expected `name: job`"));
+            continue;
+        };
+        if monkey.len() != 4 {
+            violations.push(Violation::at_line(
+                line_idx,
+                format!("monkey name {:?} is not 4 characters", monkey),
+            ));
+        }
+        names.push(monkey);
+        if job.len() == 11 {
+            if !matches!(job.as_bytes()[5], b'+' | b'-' | b'*' | b'/')
+                || job.as_bytes()[4] != b' '
+                || job.as_bytes()[6] != b' '
+            {
+                violations.push(Violation::at_line(
+                    line_idx,
+                    format!("expected `aaaa op bbbb`, found {:?}", job),
+                ));
+                continue;
+            }
+            operands.push((line_idx, &job[..4]));
+            operands.push((line_idx, &job[7..]));
+        } else if job.parse::<u64>().is_err() {
+            violations.push(Violation::at_line(
+                line_idx,
+                format!(
+                    "job {:?} is neither a number nor an 11 character operation",
+                    job
+                ),
+            ));
+        } else if monkey == "root" {
+            violations.push(Violation::at_line(line_idx, "root must be an operation"));
+        }
+    }
+    for (line_idx, operand) in operands {
+        if !names.contains(&operand) {
+            violations.push(Violation::at_line(
+                line_idx,
+                format!("job refers to unknown monkey {:?}", operand),
+            ));
+        }
+    }
+    for required in ["root", "humn"] {
+        if !names.contains(&required) {
+            violations.push(Violation::global(format!("no monkey named {}", required)));
+        }
+    }
+    violations
+}
+
+#[aoc(day21, part1)]
+pub fn part1(input: &str) -> u64 {
+    let monkeys: HashMap<String, Job> = input
+        .lines()
+        .map(|line| {
+            let (monkey, job) = line.split_once(": ").unwrap();
+            let job = if job.len() == 11 {
+                let a = job[..4].to_owned();
+                let b = job[7..].to_owned();
+                let job = job.as_bytes();
+                match job[5] {
+                    b'+' => Job::Add(a, b),
+                    b'-' => Job::Sub(a, b),
+                    b'*' => Job::Mul(a, b),
+                    b'/' => Job::Div(a, b),
+                    x => panic!("invalid op: {}", x as char),
+                }
+            } else {
+                Job::Num(job.parse().unwrap())
+            };
+            (monkey.to_owned(), job)
+        })
+        .collect();
+    eval(&monkeys, &monkeys["root"]).unwrap()
+}
+
+#[aoc(day21, part2)]
+pub fn part2(input: &str) -> u64 {
+    let mut monkeys: HashMap<String, Job> = input
+        .lines()
+        .map(|line| {
+            let (monkey, job) = line.split_once(": ").unwrap();
+            let job = if monkey == "humn" {
+                Job::UnknownVariable
+            } else if job.len() == 11 {
+                let a = job[..4].to_owned();
+                let b = job[7..].to_owned();
+                let job = job.as_bytes();
+                match job[5] {
+                    b'+' => Job::Add(a, b),
+                    b'-' => Job::Sub(a, b),
+                    b'*' => Job::Mul(a, b),
+                    b'/' => Job::Div(a, b),
+                    x => panic!("invalid op: {}", x as char),
+                }
+            } else {
+                Job::Num(job.parse().unwrap())
+            };
+            (monkey.to_owned(), job)
+        })
+        .collect();
+    *monkeys.get_mut("humn").unwrap() = Job::UnknownVariable;
+    let root = &monkeys["root"];
+    let (lhs, rhs) = match root {
+        Job::Num(_) | Job::UnknownVariable => panic!("uh oh"),
+        Job::Add(a, b) | Job::Sub(a, b) | Job::Mul(a, b) | Job::Div(a, b) => (a.clone(), b.clone()),
+    };
+    solve_equation(&monkeys, &monkeys[lhs.as_str()], &monkeys[rhs.as_str()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // Builds a chain of jobs applied to humn, choosing the known operands so that every
+    // subtraction and division is exact for the given value of humn. Returns the monkeys and
+    // the name of the monkey at the end of the chain along with its value.
+    fn build_monkeys(humn: u64, ops: &[(u8, u64, bool)]) -> (HashMap<String, Job>, String, u64) {
+        let mut monkeys = HashMap::new();
+        monkeys.insert("humn".to_owned(), Job::UnknownVariable);
+        let mut unknown = "humn".to_owned();
+        let mut value = humn;
+        for (idx, &(op, k, unknown_is_lhs)) in ops.iter().enumerate() {
+            let known = format!("k{:03}", idx);
+            let (known_value, job, new_value): (_, fn(String, String) -> Job, _) = match op {
+                0 => (k, Job::Add, value + k),
+                1 if unknown_is_lhs => (k % (value + 1), Job::Sub, value - k % (value + 1)),
+                1 => (value + k, Job::Sub, k),
+                2 => (k, Job::Mul, value * k),
+                3 if unknown_is_lhs && value.is_multiple_of(k) => (k, Job::Div, value / k),
+                3 if !unknown_is_lhs && value != 0 => (value * k, Job::Div, k),
+                _ => (k, Job::Add, value + k),
+            };
+            let (a, b) = if unknown_is_lhs {
+                (unknown.clone(), known.clone())
+            } else {
+                (known.clone(), unknown.clone())
+            };
+            monkeys.insert(known, Job::Num(known_value));
+            unknown = format!("u{:03}", idx);
+            monkeys.insert(unknown.clone(), job(a, b));
+            value = new_value;
+        }
+        (monkeys, unknown, value)
+    }
+
+    // Oracle for solve_equation: evaluate the chain for every candidate value of humn,
+    // treating inexact division and negative subtraction as having no result.
+    fn eval_exact(monkeys: &HashMap<String, Job>, name: &str, humn: u64) -> Option<u64> {
+        let operands =
+            |a: &str, b: &str| Some((eval_exact(monkeys, a, humn)?, eval_exact(monkeys, b, humn)?));
+        match &monkeys[name] {
+            Job::UnknownVariable => Some(humn),
+            Job::Num(num) => Some(*num),
+            Job::Add(a, b) => operands(a, b).map(|(a, b)| a + b),
+            Job::Sub(a, b) => operands(a, b).and_then(|(a, b)| a.checked_sub(b)),
+            Job::Mul(a, b) => operands(a, b).map(|(a, b)| a * b),
+            Job::Div(a, b) => {
+                operands(a, b).and_then(|(a, b)| (b != 0 && a.is_multiple_of(b)).then(|| a / b))
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn solve_equation_matches_brute_force(
+            humn in 0u64..200,
+            ops in prop::collection::vec((0u8..4, 1u64..20, any::<bool>()), 1..6),
+            unknown_is_lhs in any::<bool>(),
+        ) {
+            let (mut monkeys, unknown, value) = build_monkeys(humn, &ops);
+            monkeys.insert("rslt".to_owned(), Job::Num(value));
+            let (lhs, rhs) = if unknown_is_lhs {
+                (&monkeys[unknown.as_str()], &monkeys["rslt"])
+            } else {
+                (&monkeys["rslt"], &monkeys[unknown.as_str()])
+            };
+            let solution = solve_equation(&monkeys, lhs, rhs);
+            let brute_force: Vec<u64> = (0..1000)
+                .filter(|&candidate| eval_exact(&monkeys, &unknown, candidate) == Some(value))
+                .collect();
+            prop_assert_eq!(brute_force, vec![solution]);
+        }
+    }
+
+    #[test]
+    fn validation() {
+        assert_eq!(
+            validate("root: pppw + sjmn\nhumn: 5\npppw: 3"),
+            vec![Violation::at_line(
+                0,
+                "job refers to unknown monkey \"sjmn\""
+            )]
+        );
+    }
+}
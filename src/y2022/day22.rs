@@ -9,6 +9,8 @@ use std::{
 
 use aoc_runner_derive::aoc;
 
+use crate::validate::Violation;
+
 #[derive(Debug)]
 enum Step {
     Walk(usize),
@@ -195,6 +197,52 @@ impl<'a> Person<'a> {
     }
 }
 
+pub(crate) fn validate(input: &str) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    let Some((map, steps)) = input.split_once("\n\n") else {
+        return vec![Violation::global(
+            "expected the map and the path separated by a blank line",
+        )];
+    };
+    for (line_idx, line) in map.lines().enumerate() {
+        if let Some(col_idx) = line.bytes().position(|b| !matches!(b, b'.' | b'#' | b' ')) {
+            violations.push(Violation::at_line(
+                line_idx,
+                format!("invalid map tile at column {}", col_idx + 1),
+            ));
+        }
+    }
+    let height = map.lines().count();
+    let width = map.lines().map(|l| l.len()).max().unwrap_or(0);
+    if width == height {
+        violations.push(Violation::global(format!(
+            "map is square ({}x{}), expected a 4x3 or 3x4 cube net",
+            width, height
+        )));
+    } else {
+        let (long, short) = (width.max(height), width.min(height));
+        if long / 4 != short / 3 || long % 4 != 0 || short % 3 != 0 {
+            violations.push(Violation::global(format!(
+                "map is {}x{}, expected a 4x3 or 3x4 cube net of equal sized faces",
+                width, height
+            )));
+        }
+    }
+    let steps_line_idx = height + 1;
+    for (steps_idx, line) in steps.lines().enumerate() {
+        if let Some(col_idx) = line
+            .bytes()
+            .position(|b| !matches!(b, b'0'..=b'9' | b'L' | b'R'))
+        {
+            violations.push(Violation::at_line(
+                steps_line_idx + steps_idx,
+                format!("invalid path instruction at column {}", col_idx + 1),
+            ));
+        }
+    }
+    violations
+}
+
 #[aoc(day22, part1)]
 pub fn part1(input: &str) -> usize {
     let (map, steps) = input.split_once("\n\n").unwrap();
@@ -212,6 +260,7 @@ pub fn part1(input: &str) -> usize {
     1000 * row + 4 * col + person.dir as usize
 }
 
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 struct XYZ {
     x: usize,
@@ -395,7 +444,7 @@ impl Cube {
         }
     }
 
-    fn cube_iter(&self, pos: CubePos, dir: Direction) -> CubeIter {
+    fn cube_iter(&self, pos: CubePos, dir: Direction) -> CubeIter<'_> {
         let (skip, row_or_col) = match dir {
             Direction::Right => (
                 pos.face_pos % self.face_len + 1,
@@ -540,7 +589,10 @@ fn calculate_corner_coords(
             );
             _fold_neighbors(faces, width, coords, current - width);
         }
-        if current % width > 0 && faces[current - 1].is_some() && coords[current - 1].is_none() {
+        if !current.is_multiple_of(width)
+            && faces[current - 1].is_some()
+            && coords[current - 1].is_none()
+        {
             _fold(
                 faces,
                 coords,
@@ -691,3 +743,19 @@ pub fn part2(input: &str) -> usize {
         (pos.face_idx % cube.width_in_faces * cube.face_len + pos.face_pos % cube.face_len) + 1;
     1000 * row + 4 * col + dir as usize
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validation() {
+        let net = format!("{}\n10R5", "......\n".repeat(6));
+        assert_eq!(
+            validate(&net),
+            vec![Violation::global(
+                "map is square (6x6), expected a 4x3 or 3x4 cube net"
+            )]
+        );
+    }
+}
@@ -2,6 +2,8 @@ use std::{cmp::Ordering, collections::BinaryHeap, convert::Infallible, str::From
 
 use aoc_runner_derive::aoc;
 
+use crate::validate::Violation;
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[repr(u8)]
 enum Direction {
@@ -85,6 +87,79 @@ impl Map {
     }
 }
 
+pub(crate) fn validate(input: &str) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    let lines: Vec<_> = input.lines().collect();
+    if lines.len() < 3 {
+        return vec![Violation::global("expected at least 3 rows")];
+    }
+    let total_width = lines[0].len();
+    if total_width < 3 {
+        return vec![Violation::at_line(0, "expected at least 3 columns")];
+    }
+    let last_idx = lines.len() - 1;
+    let interior_wall = "#".repeat(total_width - 2);
+    let expected_top = format!("#.{}", interior_wall);
+    let expected_bottom = format!("{}.#", interior_wall);
+    for (line_idx, line) in lines.iter().enumerate() {
+        if line.len() != total_width {
+            violations.push(Violation::at_line(
+                line_idx,
+                format!("row is {} wide, expected {}", line.len(), total_width),
+            ));
+            continue;
+        }
+        if line_idx == 0 {
+            if *line != expected_top {
+                violations.push(Violation::at_line(
+                    line_idx,
+                    "top wall must be solid except for the entrance in column 2",
+                ));
+            }
+            continue;
+        }
+        if line_idx == last_idx {
+            if *line != expected_bottom {
+                violations.push(Violation::at_line(
+                    line_idx,
+                    format!(
+                        "bottom wall must be solid except for the exit in column {}",
+                        total_width - 1
+                    ),
+                ));
+            }
+            continue;
+        }
+        let row = line.as_bytes();
+        if row[0] != b'#' || row[total_width - 1] != b'#' {
+            violations.push(Violation::at_line(
+                line_idx,
+                "row must start and end with a wall",
+            ));
+        }
+        for (col_idx, &b) in row.iter().enumerate().take(total_width - 1).skip(1) {
+            match b {
+                b'.' | b'<' | b'>' => {}
+                b'^' | b'v' if col_idx == 1 || col_idx == total_width - 2 => {
+                    violations.push(Violation::at_line(
+                        line_idx,
+                        format!(
+                            "vertical blizzard at column {} would cross the entrance or exit",
+                            col_idx + 1
+                        ),
+                    ));
+                }
+                b'^' | b'v' => {}
+                _ => violations.push(Violation::at_line(
+                    line_idx,
+                    format!("invalid tile at column {}", col_idx + 1),
+                )),
+            }
+        }
+    }
+    violations
+}
+
 fn lcm(a: usize, b: usize) -> usize {
     fn gcd(a: usize, b: usize) -> usize {
         let mut max = a;
@@ -235,3 +310,20 @@ pub fn part2(input: &str) -> usize {
     let steps_back_to_start = shortest_path(&map, map.grid.len() - 1, 0, steps_to_finish);
     shortest_path(&map, 0, map.grid.len() - 1, steps_back_to_start)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validation() {
+        assert_eq!(validate("#.####\n#>.v.#\n#.<..#\n####.#"), vec![]);
+        assert_eq!(
+            validate("#.####\n#^...#\n#....#\n####.#"),
+            vec![Violation::at_line(
+                1,
+                "vertical blizzard at column 2 would cross the entrance or exit"
+            )]
+        );
+    }
+}
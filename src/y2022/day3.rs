@@ -0,0 +1,284 @@
+use std::fmt;
+
+use aoc_runner_derive::aoc;
+
+use crate::validate::Violation;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RucksackError {
+    OddLength { line: usize },
+    InvalidItem { line: usize, item: char },
+    IncompleteGroup { line: usize, group_size: usize },
+    NoBadge { line: usize },
+    ZeroGroupSize,
+}
+impl RucksackError {
+    /// 1-based line number within the input, if the error belongs to one.
+    pub fn line(&self) -> Option<usize> {
+        match *self {
+            RucksackError::OddLength { line }
+            | RucksackError::InvalidItem { line, .. }
+            | RucksackError::IncompleteGroup { line, .. }
+            | RucksackError::NoBadge { line } => Some(line),
+            RucksackError::ZeroGroupSize => None,
+        }
+    }
+
+    /// The error without its line number.
+    fn message(&self) -> String {
+        match self {
+            RucksackError::OddLength { .. } => "rucksack has an odd number of items".to_owned(),
+            RucksackError::InvalidItem { item, .. } => format!("invalid item {:?}", item),
+            RucksackError::IncompleteGroup { group_size, .. } => {
+                format!("group has fewer than {} rucksacks", group_size)
+            }
+            RucksackError::NoBadge { .. } => "group has no item in common".to_owned(),
+            RucksackError::ZeroGroupSize => "group size must be at least 1".to_owned(),
+        }
+    }
+}
+impl fmt::Display for RucksackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line() {
+            Some(line) => write!(f, "line {}: {}", line, self.message()),
+            None => write!(f, "{}", self.message()),
+        }
+    }
+}
+impl std::error::Error for RucksackError {}
+
+fn priority(item: u8) -> Option<u8> {
+    match item {
+        b'a'..=b'z' => Some(item - b'a' + 1),
+        b'A'..=b'Z' => Some(item - b'A' + 27),
+        _ => None,
+    }
+}
+
+fn item(priority: u32) -> char {
+    match priority {
+        1..=26 => (b'a' + priority as u8 - 1) as char,
+        _ => (b'A' + priority as u8 - 27) as char,
+    }
+}
+
+/// The set of item priorities in a compartment, as bits.
+#[derive(Debug, Copy, Clone)]
+struct Compartment(u64);
+impl Compartment {
+    fn new(input: &str) -> Option<Self> {
+        input.bytes().try_fold(Compartment(0), |compartment, b| {
+            Some(Compartment(compartment.0 | (1 << priority(b)?)))
+        })
+    }
+
+    fn items(self) -> Vec<char> {
+        (1..=52)
+            .filter(|pri| self.0 & (1 << pri) != 0)
+            .map(item)
+            .collect()
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Rucksack([Compartment; 2]);
+impl Rucksack {
+    fn parse(line_idx: usize, rucksack: &str) -> Result<Self, RucksackError> {
+        let line = line_idx + 1;
+        if let Some(item) = rucksack.chars().find(|c| !c.is_ascii_alphabetic()) {
+            return Err(RucksackError::InvalidItem { line, item });
+        }
+        if !rucksack.len().is_multiple_of(2) {
+            return Err(RucksackError::OddLength { line });
+        }
+        let mid = rucksack.len() / 2;
+        Ok(Rucksack([
+            Compartment::new(&rucksack[..mid]).unwrap(),
+            Compartment::new(&rucksack[mid..]).unwrap(),
+        ]))
+    }
+
+    fn shared(self) -> Compartment {
+        Compartment(self.0[0].0 & self.0[1].0)
+    }
+
+    fn all(self) -> Compartment {
+        Compartment(self.0[0].0 | self.0[1].0)
+    }
+}
+
+fn parse_rucksacks(input: &str) -> impl Iterator<Item = Result<Rucksack, RucksackError>> + '_ {
+    input
+        .lines()
+        .enumerate()
+        .map(|(line_idx, rucksack)| Rucksack::parse(line_idx, rucksack))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RucksackReport {
+    /// 1-based line number within the input.
+    pub line: usize,
+    /// Every item found in both compartments.
+    pub shared: Vec<char>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupReport {
+    /// 1-based line number of the group's first rucksack.
+    pub first_line: usize,
+    /// Every item carried by all rucksacks in the group.
+    pub badges: Vec<char>,
+    /// Number of distinct items carried by more than one rucksack in the group.
+    pub in_multiple_rucksacks: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Analysis {
+    pub rucksacks: Vec<RucksackReport>,
+    pub groups: Vec<GroupReport>,
+}
+
+/// Splits the rucksacks into groups of `group_size`, paired with the 1-based
+/// line of each group's first rucksack. Every group must be complete.
+fn groups(
+    rucksacks: &[Rucksack],
+    group_size: usize,
+) -> Result<Vec<(usize, &[Rucksack])>, RucksackError> {
+    if group_size == 0 {
+        return Err(RucksackError::ZeroGroupSize);
+    }
+    rucksacks
+        .chunks(group_size)
+        .enumerate()
+        .map(|(group_idx, group)| {
+            let first_line = group_idx * group_size + 1;
+            if group.len() < group_size {
+                return Err(RucksackError::IncompleteGroup {
+                    line: first_line,
+                    group_size,
+                });
+            }
+            Ok((first_line, group))
+        })
+        .collect()
+}
+
+fn badges(group: &[Rucksack]) -> u64 {
+    group
+        .iter()
+        .fold(!0, |common, rucksack| common & rucksack.all().0)
+}
+
+pub fn analyze(input: &str, group_size: usize) -> Result<Analysis, RucksackError> {
+    let rucksacks = parse_rucksacks(input).collect::<Result<Vec<_>, _>>()?;
+    let mut groups_report = Vec::new();
+    for (first_line, group) in groups(&rucksacks, group_size)? {
+        let (mut seen, mut seen_again) = (0, 0);
+        for rucksack in group {
+            seen_again |= seen & rucksack.all().0;
+            seen |= rucksack.all().0;
+        }
+        groups_report.push(GroupReport {
+            first_line,
+            badges: Compartment(badges(group)).items(),
+            in_multiple_rucksacks: seen_again.count_ones() as usize,
+        });
+    }
+    let rucksacks = rucksacks
+        .iter()
+        .enumerate()
+        .map(|(line_idx, rucksack)| RucksackReport {
+            line: line_idx + 1,
+            shared: rucksack.shared().items(),
+        })
+        .collect();
+    Ok(Analysis {
+        rucksacks,
+        groups: groups_report,
+    })
+}
+
+/// Sum of the priority of the first badge of each group of `group_size`.
+pub fn badge_priorities(input: &str, group_size: usize) -> Result<usize, RucksackError> {
+    let rucksacks = parse_rucksacks(input).collect::<Result<Vec<_>, _>>()?;
+    groups(&rucksacks, group_size)?
+        .into_iter()
+        .map(|(first_line, group)| match badges(group) {
+            0 => Err(RucksackError::NoBadge { line: first_line }),
+            badges => Ok(badges.trailing_zeros() as usize),
+        })
+        .sum()
+}
+
+pub(crate) fn validate(input: &str) -> Vec<Violation> {
+    let mut violations: Vec<_> = parse_rucksacks(input)
+        .filter_map(Result::err)
+        .map(|err| Violation::at_line(err.line().unwrap() - 1, err.message()))
+        .collect();
+    if !input.lines().count().is_multiple_of(3) {
+        violations.push(Violation::global(
+            "number of rucksacks is not a multiple of 3",
+        ));
+    }
+    violations
+}
+
+#[aoc(day3, part1)]
+pub fn part1(input: &str) -> usize {
+    parse_rucksacks(input)
+        .map(|rucksack| rucksack.unwrap().shared().0.trailing_zeros() as usize)
+        .sum()
+}
+
+#[aoc(day3, part2)]
+pub fn part2(input: &str) -> usize {
+    badge_priorities(input, 3).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn analysis() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn";
+        let analysis = analyze(input, 2).unwrap();
+        assert_eq!(analysis.rucksacks[0].shared, vec!['p']);
+        assert_eq!(analysis.groups[0].badges, vec!['f', 'r', 's', 'F', 'M']);
+        assert_eq!(analysis.groups[0].in_multiple_rucksacks, 5);
+        assert_eq!(
+            analyze(input, 3),
+            Err(RucksackError::IncompleteGroup {
+                line: 4,
+                group_size: 3
+            })
+        );
+        assert_eq!(badge_priorities(input, 2), Ok(6 + 17));
+        assert_eq!(
+            badge_priorities(input, 3),
+            Err(RucksackError::IncompleteGroup {
+                line: 4,
+                group_size: 3
+            })
+        );
+        assert_eq!(
+            badge_priorities("ab\ncd\nef", 3),
+            Err(RucksackError::NoBadge { line: 1 })
+        );
+        assert_eq!(analyze(input, 0), Err(RucksackError::ZeroGroupSize));
+        assert_eq!(analyze("abc", 1), Err(RucksackError::OddLength { line: 1 }));
+        assert_eq!(
+            analyze("ab\nabcé", 1),
+            Err(RucksackError::InvalidItem {
+                line: 2, item: 'é'
+            })
+        );
+        assert_eq!(
+            validate("abc\nab"),
+            vec![
+                Violation::at_line(0, "rucksack has an odd number of items"),
+                Violation::global("number of rucksacks is not a multiple of 3"),
+            ]
+        );
+    }
+}
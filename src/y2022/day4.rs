@@ -0,0 +1,52 @@
+use std::ops::RangeInclusive;
+
+use aoc_runner_derive::aoc;
+
+use crate::interval::IntervalSet;
+
+/// One line of assignments. The puzzle has pairs but any number works.
+pub fn parse_group(input: &str) -> IntervalSet {
+    input.split(',').map(parse_range).collect()
+}
+
+fn parse_range(input: &str) -> RangeInclusive<u64> {
+    let (begin, end) = input.split_once('-').unwrap();
+    let begin: u64 = begin.parse().unwrap();
+    let end: u64 = end.parse().unwrap();
+    begin..=end
+}
+
+/// Whether some assignment in the group covers all of another one.
+pub fn has_contained_assignment(group: &IntervalSet) -> bool {
+    let assignments = group.assignments();
+    assignments.iter().enumerate().any(|(idx, inner)| {
+        group.overlapping(inner).into_iter().any(|other| {
+            let outer = &assignments[other];
+            other != idx && outer.start() <= inner.start() && outer.end() >= inner.end()
+        })
+    })
+}
+
+/// Whether any two assignments in the group share a section.
+pub fn has_overlap(group: &IntervalSet) -> bool {
+    let total: u64 = group
+        .assignments()
+        .iter()
+        .map(|range| range.end() - range.start() + 1)
+        .sum();
+    group.len() < total
+}
+
+#[aoc(day4, part1)]
+pub fn part1(input: &str) -> usize {
+    input
+        .lines()
+        .map(parse_group)
+        .filter(has_contained_assignment)
+        .count()
+}
+
+#[aoc(day4, part2)]
+pub fn part2(input: &str) -> usize {
+    input.lines().map(parse_group).filter(has_overlap).count()
+}
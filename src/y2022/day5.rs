@@ -0,0 +1,745 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    fmt,
+    str::FromStr,
+};
+
+use aoc_runner_derive::aoc;
+
+/// Moves crates between stacks. Implementations only need to say how a
+/// single, already validated move rearranges the two stacks.
+pub trait Crane {
+    /// Move the top `quantity` crates of `from` onto `to`.
+    fn lift(&self, from: &mut Vec<char>, to: &mut Vec<char>, quantity: usize);
+
+    /// The most crates a single move may shift, if limited.
+    fn max_lift(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// Moves crates one at a time, reversing their order.
+#[derive(Debug, Copy, Clone)]
+pub struct CrateMover9000;
+impl Crane for CrateMover9000 {
+    fn lift(&self, from: &mut Vec<char>, to: &mut Vec<char>, quantity: usize) {
+        for _ in 0..quantity {
+            to.push(from.pop().unwrap());
+        }
+    }
+}
+
+/// Moves crates as a block, keeping their order.
+#[derive(Debug, Copy, Clone)]
+pub struct CrateMover9001;
+impl Crane for CrateMover9001 {
+    fn lift(&self, from: &mut Vec<char>, to: &mut Vec<char>, quantity: usize) {
+        let offset = from.len() - quantity;
+        to.extend_from_slice(&from[offset..]);
+        from.truncate(offset);
+    }
+}
+
+/// Another crane that can only shift `max_lift` crates per move.
+#[derive(Debug, Copy, Clone)]
+pub struct Limited<C> {
+    pub crane: C,
+    pub max_lift: usize,
+}
+impl<C: Crane> Crane for Limited<C> {
+    fn lift(&self, from: &mut Vec<char>, to: &mut Vec<char>, quantity: usize) {
+        self.crane.lift(from, to, quantity)
+    }
+
+    fn max_lift(&self) -> Option<usize> {
+        Some(
+            self.crane
+                .max_lift()
+                .map_or(self.max_lift, |max| max.min(self.max_lift)),
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoveError {
+    /// A line that doesn't read as `move N from A to B`.
+    Malformed {
+        text: String,
+        reason: &'static str,
+    },
+    NoSuchStack {
+        stack: usize,
+    },
+    SameStack {
+        stack: usize,
+    },
+    NotEnoughCrates {
+        stack: usize,
+        available: usize,
+        requested: usize,
+    },
+    OverCapacity {
+        requested: usize,
+        max_lift: usize,
+    },
+}
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Stacks are reported 1-based, as in the drawing.
+        match self {
+            MoveError::Malformed { text, reason } => write!(f, "{}: {:?}", reason, text),
+            MoveError::NoSuchStack { stack } => write!(f, "there is no stack {}", stack + 1),
+            MoveError::SameStack { stack } => write!(f, "moves stack {} onto itself", stack + 1),
+            MoveError::NotEnoughCrates {
+                stack,
+                available,
+                requested,
+            } => write!(
+                f,
+                "moves {} crates from stack {} which only has {}",
+                requested,
+                stack + 1,
+                available
+            ),
+            MoveError::OverCapacity {
+                requested,
+                max_lift,
+            } => write!(
+                f,
+                "moves {} crates but the crane can only lift {}",
+                requested, max_lift
+            ),
+        }
+    }
+}
+impl std::error::Error for MoveError {}
+
+/// The first move of a procedure that could not be carried out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IllegalMove {
+    /// 0-based index of the move within the procedure.
+    pub step: usize,
+    pub error: MoveError,
+}
+impl fmt::Display for IllegalMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "step {}: {}", self.step + 1, self.error)
+    }
+}
+impl std::error::Error for IllegalMove {}
+
+/// Why a puzzle input couldn't be run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProcedureError {
+    /// No blank line between the drawing and the moves.
+    MissingSeparator,
+    Drawing(DrawingError),
+    IllegalMove(IllegalMove),
+}
+impl fmt::Display for ProcedureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProcedureError::MissingSeparator => {
+                write!(f, "expected a blank line after the stack drawing")
+            }
+            ProcedureError::Drawing(err) => write!(f, "{}", err),
+            ProcedureError::IllegalMove(illegal) => write!(f, "{}", illegal),
+        }
+    }
+}
+impl std::error::Error for ProcedureError {}
+impl From<DrawingError> for ProcedureError {
+    fn from(err: DrawingError) -> Self {
+        ProcedureError::Drawing(err)
+    }
+}
+impl From<IllegalMove> for ProcedureError {
+    fn from(illegal: IllegalMove) -> Self {
+        ProcedureError::IllegalMove(illegal)
+    }
+}
+
+/// A crate in the stack drawing that is cut short, like `[A`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DrawingError {
+    /// 1-based line number within the drawing.
+    pub line: usize,
+}
+impl fmt::Display for DrawingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: crate is cut short", self.line)
+    }
+}
+impl std::error::Error for DrawingError {}
+
+/// Splits the puzzle input into the stack drawing and the moves.
+fn split_input(input: &str) -> Result<(&str, &str), ProcedureError> {
+    input
+        .split_once("\n\n")
+        .or_else(|| input.split_once("\r\n\r\n"))
+        .ok_or(ProcedureError::MissingSeparator)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Stacks(Vec<Vec<char>>);
+impl Stacks {
+    fn get_from_and_to_stacks(
+        &mut self,
+        from_idx: usize,
+        to_idx: usize,
+    ) -> (&mut Vec<char>, &mut Vec<char>) {
+        assert_ne!(from_idx, to_idx);
+        let (first, second) = self.0.split_at_mut(from_idx.max(to_idx));
+        if from_idx < to_idx {
+            (&mut first[from_idx], &mut second[0])
+        } else {
+            (&mut second[0], &mut first[to_idx])
+        }
+    }
+
+    /// The crate on top of each stack, with a space for empty stacks.
+    pub fn top_crates(&self) -> String {
+        self.0
+            .iter()
+            .map(|v| v.last().copied().unwrap_or(' '))
+            .collect()
+    }
+
+    pub fn check(&self, crane: &impl Crane, mov: &Move) -> Result<(), MoveError> {
+        for stack in [mov.from_stack_idx, mov.to_stack_idx] {
+            if stack >= self.0.len() {
+                return Err(MoveError::NoSuchStack { stack });
+            }
+        }
+        if mov.from_stack_idx == mov.to_stack_idx {
+            return Err(MoveError::SameStack {
+                stack: mov.from_stack_idx,
+            });
+        }
+        let available = self.0[mov.from_stack_idx].len();
+        if mov.quantity > available {
+            return Err(MoveError::NotEnoughCrates {
+                stack: mov.from_stack_idx,
+                available,
+                requested: mov.quantity,
+            });
+        }
+        match crane.max_lift() {
+            Some(max_lift) if mov.quantity > max_lift => Err(MoveError::OverCapacity {
+                requested: mov.quantity,
+                max_lift,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn apply(&mut self, crane: &impl Crane, mov: &Move) -> Result<(), MoveError> {
+        self.check(crane, mov)?;
+        let (from_stack, to_stack) =
+            self.get_from_and_to_stacks(mov.from_stack_idx, mov.to_stack_idx);
+        crane.lift(from_stack, to_stack, mov.quantity);
+        Ok(())
+    }
+}
+
+impl FromStr for Stacks {
+    type Err = DrawingError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        // The label line also counts stacks that start out empty.
+        let num_labels = input
+            .lines()
+            .last()
+            .unwrap_or("")
+            .split_whitespace()
+            .count();
+        let mut stacks = vec![Vec::new(); num_labels];
+        let lines: Vec<_> = input.lines().collect();
+        for (line_idx, line) in lines.iter().enumerate().rev().skip(1) {
+            for (stack_idx, cell) in line.as_bytes().chunks(4).enumerate() {
+                let crt = match cell {
+                    [_, crt, _, ..] => *crt as char,
+                    _ if cell.iter().all(|&b| b == b' ') => continue,
+                    _ => return Err(DrawingError { line: line_idx + 1 }),
+                };
+                if crt == ' ' {
+                    continue;
+                }
+                if stack_idx >= stacks.len() {
+                    stacks.resize(stack_idx + 1, Vec::new());
+                }
+                stacks[stack_idx].push(crt);
+            }
+        }
+        Ok(Stacks(stacks))
+    }
+}
+
+/// Prints the same drawing that `from_str` reads.
+impl fmt::Display for Stacks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let height = self.0.iter().map(Vec::len).max().unwrap_or(0);
+        for row in (0..height).rev() {
+            let cells: Vec<_> = self
+                .0
+                .iter()
+                .map(|stack| match stack.get(row) {
+                    Some(crt) => format!("[{}]", crt),
+                    None => "   ".to_string(),
+                })
+                .collect();
+            writeln!(f, "{}", cells.join(" "))?;
+        }
+        let labels: Vec<_> = (1..=self.0.len())
+            .map(|label| format!(" {} ", label))
+            .collect();
+        write!(f, "{}", labels.join(" "))
+    }
+}
+
+/// How one stack differs between two states. Both share the crates below
+/// `removed` and `added`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackDiff {
+    pub stack: usize,
+    pub removed: Vec<char>,
+    pub added: Vec<char>,
+}
+impl fmt::Display for StackDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "stack {}:", self.stack + 1)?;
+        for crt in &self.removed {
+            write!(f, " -[{}]", crt)?;
+        }
+        for crt in &self.added {
+            write!(f, " +[{}]", crt)?;
+        }
+        Ok(())
+    }
+}
+
+impl Stacks {
+    /// The stacks that differ between `self` and `other`, bottom to top.
+    pub fn diff(&self, other: &Stacks) -> Vec<StackDiff> {
+        let empty = Vec::new();
+        (0..self.0.len().max(other.0.len()))
+            .filter_map(|stack| {
+                let before = self.0.get(stack).unwrap_or(&empty);
+                let after = other.0.get(stack).unwrap_or(&empty);
+                let common = before.iter().zip(after).take_while(|(a, b)| a == b).count();
+                (before.len() != common || after.len() != common).then(|| StackDiff {
+                    stack,
+                    removed: before[common..].to_vec(),
+                    added: after[common..].to_vec(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Step {
+    mov: Move,
+    lifted: Vec<char>, // the crates taken off the source stack, bottom first
+}
+
+/// A simulation that records each move so it can be stepped backwards and
+/// forwards.
+#[derive(Debug, Clone)]
+pub struct History<C> {
+    crane: C,
+    stacks: Stacks,
+    steps: Vec<Step>,
+    position: usize, // number of steps currently applied
+}
+impl<C: Crane> History<C> {
+    pub fn new(stacks: Stacks, crane: C) -> Self {
+        History {
+            crane,
+            stacks,
+            steps: Vec::new(),
+            position: 0,
+        }
+    }
+
+    /// Record the whole puzzle procedure, leaving the history at its end.
+    pub fn from_input(input: &str, crane: C) -> Result<Self, ProcedureError> {
+        let (stack_input, moves) = split_input(input)?;
+        let mut history = History::new(stack_input.parse()?, crane);
+        for (step, mov) in moves.lines().enumerate() {
+            mov.parse()
+                .and_then(|mov| history.apply(mov))
+                .map_err(|error| IllegalMove { step, error })?;
+        }
+        Ok(history)
+    }
+
+    pub fn stacks(&self) -> &Stacks {
+        &self.stacks
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Number of recorded steps, including any that have been undone.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Make a new move, dropping any undone steps.
+    pub fn apply(&mut self, mov: Move) -> Result<(), MoveError> {
+        self.stacks.check(&self.crane, &mov)?;
+        let from_stack = &self.stacks.0[mov.from_stack_idx];
+        let lifted = from_stack[from_stack.len() - mov.quantity..].to_vec();
+        self.stacks.apply(&self.crane, &mov).unwrap();
+        self.steps.truncate(self.position);
+        self.steps.push(Step { mov, lifted });
+        self.position += 1;
+        Ok(())
+    }
+
+    pub fn undo(&mut self) -> bool {
+        let Some(step) = self.position.checked_sub(1).map(|idx| &self.steps[idx]) else {
+            return false;
+        };
+        let to_stack = &mut self.stacks.0[step.mov.to_stack_idx];
+        to_stack.truncate(to_stack.len() - step.mov.quantity);
+        self.stacks.0[step.mov.from_stack_idx].extend_from_slice(&step.lifted);
+        self.position -= 1;
+        true
+    }
+
+    pub fn redo(&mut self) -> bool {
+        let Some(step) = self.steps.get(self.position) else {
+            return false;
+        };
+        self.stacks.apply(&self.crane, &step.mov).unwrap();
+        self.position += 1;
+        true
+    }
+
+    /// Move to the state after the first `position` steps, if there are that
+    /// many.
+    pub fn seek(&mut self, position: usize) -> bool {
+        if position > self.steps.len() {
+            return false;
+        }
+        while self.position > position {
+            self.undo();
+        }
+        while self.position < position {
+            self.redo();
+        }
+        true
+    }
+
+    pub fn moves(&self) -> impl Iterator<Item = &Move> + '_ {
+        self.steps.iter().map(|step| &step.mov)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    quantity: usize,
+    from_stack_idx: usize,
+    to_stack_idx: usize,
+}
+impl FromStr for Move {
+    type Err = MoveError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let malformed = |reason| MoveError::Malformed {
+            text: input.to_string(),
+            reason,
+        };
+        let rest = input.strip_prefix("move ").unwrap_or(input);
+        let (quantity, from_stack, to_stack) = rest
+            .split_once(" from ")
+            .and_then(|(quantity, rest)| {
+                let (from_stack, to_stack) = rest.split_once(" to ")?;
+                Some((quantity, from_stack, to_stack))
+            })
+            .ok_or_else(|| malformed("expected `move N from A to B`"))?;
+        // Stacks are numbered from 1.
+        let stack_idx = |stack: &str| match stack.trim().parse::<usize>() {
+            Ok(stack) if stack > 0 => Ok(stack - 1),
+            _ => Err(malformed("invalid stack number")),
+        };
+        Ok(Move {
+            quantity: quantity
+                .trim()
+                .parse()
+                .map_err(|_| malformed("invalid quantity"))?,
+            from_stack_idx: stack_idx(from_stack)?,
+            to_stack_idx: stack_idx(to_stack)?,
+        })
+    }
+}
+
+/// Writes the move the way `from_str` reads it.
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.quantity,
+            self.from_stack_idx + 1,
+            self.to_stack_idx + 1
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanError {
+    /// No sequence of moves turns the start into the target.
+    Unreachable,
+    /// Gave up after visiting this many arrangements.
+    SearchLimit(usize),
+}
+impl fmt::Display for PlanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlanError::Unreachable => write!(f, "the target arrangement can't be reached"),
+            PlanError::SearchLimit(limit) => {
+                write!(f, "no plan found within {} arrangements", limit)
+            }
+        }
+    }
+}
+impl std::error::Error for PlanError {}
+
+/// A shortest sequence of moves that `crane` can make to turn `start` into
+/// `target`, found by breadth-first search over at most `max_states`
+/// arrangements.
+pub fn plan(
+    start: &Stacks,
+    target: &Stacks,
+    crane: &impl Crane,
+    max_states: usize,
+) -> Result<Vec<Move>, PlanError> {
+    let sorted_crates = |stacks: &Stacks| {
+        let mut crates: Vec<char> = stacks.0.iter().flatten().copied().collect();
+        crates.sort_unstable();
+        crates
+    };
+    if start.0.len() != target.0.len() || sorted_crates(start) != sorted_crates(target) {
+        return Err(PlanError::Unreachable);
+    }
+
+    // Each visited arrangement with the index of its parent and the move from it.
+    let mut visited: Vec<(Stacks, Option<(usize, Move)>)> = vec![(start.clone(), None)];
+    let mut seen: HashSet<Stacks> = HashSet::from([start.clone()]);
+    let mut queue = VecDeque::from([0]);
+    while let Some(idx) = queue.pop_front() {
+        if visited[idx].0 == *target {
+            let mut moves = Vec::new();
+            let mut idx = idx;
+            while let Some((parent, mov)) = visited[idx].1 {
+                moves.push(mov);
+                idx = parent;
+            }
+            moves.reverse();
+            return Ok(moves);
+        }
+        let num_stacks = visited[idx].0 .0.len();
+        for from_stack_idx in 0..num_stacks {
+            for to_stack_idx in (0..num_stacks).filter(|&to| to != from_stack_idx) {
+                for quantity in 1..=visited[idx].0 .0[from_stack_idx].len() {
+                    let mov = Move {
+                        quantity,
+                        from_stack_idx,
+                        to_stack_idx,
+                    };
+                    let mut next = visited[idx].0.clone();
+                    if next.apply(crane, &mov).is_err() || seen.contains(&next) {
+                        continue;
+                    }
+                    if visited.len() == max_states {
+                        return Err(PlanError::SearchLimit(max_states));
+                    }
+                    seen.insert(next.clone());
+                    visited.push((next, Some((idx, mov))));
+                    queue.push_back(visited.len() - 1);
+                }
+            }
+        }
+    }
+    Err(PlanError::Unreachable)
+}
+
+/// Run the whole puzzle input with `crane`, stopping at the first move that
+/// can't be made.
+pub fn simulate(input: &str, crane: &impl Crane) -> Result<Stacks, ProcedureError> {
+    let (stack_input, moves) = split_input(input)?;
+    let mut stacks: Stacks = stack_input.parse()?;
+    for (step, mov) in moves.lines().enumerate() {
+        mov.parse()
+            .and_then(|mov| stacks.apply(crane, &mov))
+            .map_err(|error| IllegalMove { step, error })?;
+    }
+    Ok(stacks)
+}
+
+#[aoc(day5, part1)]
+pub fn part1(input: &str) -> String {
+    simulate(input, &CrateMover9000).unwrap().top_crates()
+}
+
+#[aoc(day5, part2)]
+pub fn part2(input: &str) -> String {
+    simulate(input, &CrateMover9001).unwrap().top_crates()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\nmove 1 from 1 to 2";
+
+    #[test]
+    fn cranes() {
+        assert_eq!(part1(EXAMPLE), "CMZ");
+        assert_eq!(part2(EXAMPLE), "MCD");
+        let limited = Limited {
+            crane: CrateMover9001,
+            max_lift: 2,
+        };
+        assert_eq!(
+            simulate(EXAMPLE, &limited),
+            Err(ProcedureError::IllegalMove(IllegalMove {
+                step: 1,
+                error: MoveError::OverCapacity {
+                    requested: 3,
+                    max_lift: 2
+                },
+            }))
+        );
+        let bad = EXAMPLE.replace("move 2 from 2 to 1", "move 3 from 2 to 1");
+        assert_eq!(
+            simulate(&bad, &CrateMover9000),
+            Err(ProcedureError::IllegalMove(IllegalMove {
+                step: 2,
+                error: MoveError::NotEnoughCrates {
+                    stack: 1,
+                    available: 2,
+                    requested: 3
+                },
+            }))
+        );
+        let crlf = EXAMPLE.replace('\n', "\r\n");
+        assert_eq!(part1(&crlf), "CMZ");
+        assert_eq!(
+            History::from_input(&crlf, CrateMover9000)
+                .unwrap()
+                .stacks()
+                .top_crates(),
+            "CMZ"
+        );
+        let no_moves = EXAMPLE.split_once("\n\n").unwrap().0;
+        assert_eq!(
+            simulate(no_moves, &CrateMover9000),
+            Err(ProcedureError::MissingSeparator)
+        );
+        assert_eq!(
+            History::from_input(no_moves, CrateMover9000).unwrap_err(),
+            ProcedureError::MissingSeparator
+        );
+        for (line, reason) in [
+            ("move 1 from 0 to 1", "invalid stack number"),
+            ("move x from 1 to 2", "invalid quantity"),
+            ("move 1 from 1", "expected `move N from A to B`"),
+        ] {
+            let bad = EXAMPLE.replace("move 3 from 1 to 3", line);
+            let expected = ProcedureError::IllegalMove(IllegalMove {
+                step: 1,
+                error: MoveError::Malformed {
+                    text: line.to_string(),
+                    reason,
+                },
+            });
+            assert_eq!(simulate(&bad, &CrateMover9000), Err(expected.clone()));
+            assert_eq!(
+                History::from_input(&bad, CrateMover9000).unwrap_err(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn history() {
+        let stack_input = EXAMPLE.split_once("\n\n").unwrap().0;
+        let start: Stacks = stack_input.parse().unwrap();
+        assert_eq!(start.to_string(), stack_input);
+
+        let mut history = History::from_input(EXAMPLE, CrateMover9000).unwrap();
+        let end = history.stacks().clone();
+        assert_eq!(end.top_crates(), "CMZ");
+        assert_eq!(end.to_string().parse::<Stacks>().unwrap(), end);
+
+        assert!(history.seek(0));
+        assert_eq!(history.stacks(), &start);
+        assert!(!history.undo());
+        assert!(history.redo());
+        assert_eq!(
+            start.diff(history.stacks()),
+            vec![
+                StackDiff {
+                    stack: 0,
+                    removed: vec![],
+                    added: vec!['D'],
+                },
+                StackDiff {
+                    stack: 1,
+                    removed: vec!['D'],
+                    added: vec![],
+                },
+            ]
+        );
+        assert!(history.seek(history.len()));
+        assert_eq!(history.stacks(), &end);
+        assert!(!history.redo());
+        assert!(!history.seek(history.len() + 1));
+        assert_eq!(history.stacks(), &end);
+
+        assert_eq!(
+            "[A] [\n 1   2 ".parse::<Stacks>(),
+            Err(DrawingError { line: 1 })
+        );
+        assert_eq!(
+            simulate(
+                "    [B]\n[A] [C\n 1   2 \n\nmove 1 from 2 to 1",
+                &CrateMover9000
+            ),
+            Err(ProcedureError::Drawing(DrawingError { line: 2 }))
+        );
+    }
+
+    fn check_plan(crane: &impl Crane) {
+        let stack_input = EXAMPLE.split_once("\n\n").unwrap().0;
+        let start: Stacks = stack_input.parse().unwrap();
+        let target = simulate(EXAMPLE, crane).unwrap();
+        let moves = plan(&start, &target, crane, 100_000).unwrap();
+        assert!(moves.len() <= 4);
+        let procedure: Vec<_> = moves.iter().map(Move::to_string).collect();
+        let replayed = format!("{}\n\n{}", stack_input, procedure.join("\n"));
+        assert_eq!(simulate(&replayed, crane).unwrap(), target);
+
+        let mut missing = target.clone();
+        missing.0[0].pop();
+        assert_eq!(
+            plan(&start, &missing, crane, 100_000),
+            Err(PlanError::Unreachable)
+        );
+    }
+
+    #[test]
+    fn planner() {
+        check_plan(&CrateMover9000);
+        check_plan(&CrateMover9001);
+    }
+}
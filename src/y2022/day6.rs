@@ -0,0 +1,112 @@
+use std::io::{self, BufReader, Bytes, Read};
+
+use aoc_runner_derive::aoc;
+
+/// Finds windows of `window_len` distinct bytes in a byte stream, keeping a
+/// count of each byte in the current window so each byte costs O(1).
+#[derive(Debug, Clone)]
+pub struct MarkerDetector {
+    counts: [u32; 256],
+    window: Vec<u8>, // ring buffer of the last `window.len()` bytes
+    distinct: usize, // number of non-zero `counts`
+    consumed: usize,
+}
+impl MarkerDetector {
+    pub fn new(window_len: usize) -> Self {
+        assert!(window_len > 0, "uh oh");
+        MarkerDetector {
+            counts: [0; 256],
+            window: vec![0; window_len],
+            distinct: 0,
+            consumed: 0,
+        }
+    }
+
+    /// Feed the next byte. If it completes a marker, returns the number of
+    /// bytes consumed so far, which is how the puzzle reports positions.
+    pub fn push(&mut self, b: u8) -> Option<usize> {
+        let slot = self.consumed % self.window.len();
+        if self.consumed >= self.window.len() {
+            let old = self.window[slot] as usize;
+            self.counts[old] -= 1;
+            if self.counts[old] == 0 {
+                self.distinct -= 1;
+            }
+        }
+        self.window[slot] = b;
+        self.counts[b as usize] += 1;
+        if self.counts[b as usize] == 1 {
+            self.distinct += 1;
+        }
+        self.consumed += 1;
+        (self.distinct == self.window.len()).then_some(self.consumed)
+    }
+}
+
+/// Every marker position in a stream, in order.
+pub struct Markers<R> {
+    bytes: Bytes<BufReader<R>>,
+    detector: MarkerDetector,
+}
+impl<R: Read> Iterator for Markers<R> {
+    type Item = io::Result<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for b in &mut self.bytes {
+            match b {
+                Ok(b) => {
+                    if let Some(position) = self.detector.push(b) {
+                        return Some(Ok(position));
+                    }
+                }
+                Err(err) => return Some(Err(err)),
+            }
+        }
+        None
+    }
+}
+
+pub fn markers<R: Read>(reader: R, window_len: usize) -> Markers<R> {
+    Markers {
+        bytes: BufReader::new(reader).bytes(),
+        detector: MarkerDetector::new(window_len),
+    }
+}
+
+fn find_marker(input: &str, marker_len: usize) -> usize {
+    markers(input.as_bytes(), marker_len)
+        .next()
+        .unwrap()
+        .unwrap()
+}
+
+#[aoc(day6, part1)]
+pub fn part1(input: &str) -> usize {
+    find_marker(input, 4)
+}
+
+#[aoc(day6, part2)]
+pub fn part2(input: &str) -> usize {
+    find_marker(input, 14)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::HashSet;
+
+    proptest! {
+        #[test]
+        fn matches_windows(input in prop::collection::vec(0u8..48, 0..200), window_len in 1usize..40) {
+            let expected: Vec<_> = input
+                .windows(window_len)
+                .enumerate()
+                .filter(|(_, window)| window.iter().collect::<HashSet<_>>().len() == window_len)
+                .map(|(idx, _)| idx + window_len)
+                .collect();
+            let found: Vec<_> = markers(&input[..], window_len).map(Result::unwrap).collect();
+            prop_assert_eq!(found, expected);
+        }
+    }
+}
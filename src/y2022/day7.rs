@@ -0,0 +1,645 @@
+use std::{
+    cmp::Reverse,
+    convert::Infallible,
+    fmt::{self, Write},
+    str::FromStr,
+};
+
+use aoc_runner_derive::aoc;
+
+use crate::{topk::TopKExt, validate::Violation};
+
+#[derive(Debug)]
+pub struct Dir {
+    idx: DirIdx,
+    parent_dir: Option<DirIdx>,
+    child_dirs: Vec<(String, DirIdx)>,
+    files: Vec<(String, usize)>,
+    size: usize, // total of everything below, filled in after reconstruction
+}
+impl Dir {
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct DirIdx(usize);
+
+/// Something in a transcript that doesn't add up, like a file whose size
+/// changes between listings. Reconstruction carries on past these.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inconsistency {
+    /// 1-based line number within the transcript.
+    pub line: usize,
+    pub message: String,
+}
+impl fmt::Display for Inconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+pub struct Filesystem(Vec<Dir>);
+impl FromStr for Filesystem {
+    type Err = Infallible;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Ok(Filesystem::reconstruct(input).0)
+    }
+}
+
+impl Filesystem {
+    fn new() -> Self {
+        Filesystem(vec![Dir {
+            idx: DirIdx(0),
+            parent_dir: None,
+            child_dirs: Vec::new(),
+            files: Vec::new(),
+            size: 0,
+        }])
+    }
+
+    /// Rebuild the filesystem from a shell transcript, collecting anything
+    /// inconsistent along the way.
+    pub fn reconstruct(input: &str) -> (Self, Vec<Inconsistency>) {
+        let mut fs = Filesystem::new();
+        let mut inconsistencies = Vec::new();
+        let mut current_dir = DirIdx(0);
+        for (line_idx, line) in input.lines().enumerate() {
+            let mut report = |message: String| {
+                inconsistencies.push(Inconsistency {
+                    line: line_idx + 1,
+                    message,
+                })
+            };
+            match line.split_once(' ') {
+                Some(("$", "ls")) => {}
+                Some(("$", cmd)) => match cmd.strip_prefix("cd ") {
+                    Some(path) => match fs.resolve(current_dir, path) {
+                        Ok(dir) => current_dir = dir,
+                        Err(message) => report(message),
+                    },
+                    None => report(format!("unknown command {:?}", cmd)),
+                },
+                Some(("dir", name)) => {
+                    if let Err(message) = fs.child_dir(current_dir, name) {
+                        report(message);
+                    }
+                }
+                Some((size, name)) => match size.parse() {
+                    Ok(size) => {
+                        if let Err(message) = fs.add_file(current_dir, name, size) {
+                            report(message);
+                        }
+                    }
+                    Err(_) => report(format!("malformed listing {:?}", line)),
+                },
+                None => report(format!("malformed line {:?}", line)),
+            }
+        }
+        fs.compute_sizes();
+        (fs, inconsistencies)
+    }
+
+    /// Children are always created after their parent, so walking backwards
+    /// sees every child before its parent.
+    fn compute_sizes(&mut self) {
+        for idx in (0..self.0.len()).rev() {
+            let dir = &self.0[idx];
+            let size = dir.files.iter().map(|(_name, size)| size).sum::<usize>()
+                + dir
+                    .child_dirs
+                    .iter()
+                    .map(|(_name, child)| self.0[child.0].size)
+                    .sum::<usize>();
+            self.0[idx].size = size;
+        }
+    }
+
+    /// Follow `path` from `dir` like `cd` would, creating directories that
+    /// haven't been listed yet.
+    fn resolve(&mut self, dir: DirIdx, path: &str) -> Result<DirIdx, String> {
+        let mut dir = if path.starts_with('/') {
+            DirIdx(0)
+        } else {
+            dir
+        };
+        for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+            dir = match segment {
+                "." => dir,
+                // `cd ..` at the root stays at the root, as in a shell.
+                ".." => self.0[dir.0].parent_dir.unwrap_or(dir),
+                name => self.child_dir(dir, name)?,
+            };
+        }
+        Ok(dir)
+    }
+
+    fn child_dir(&mut self, parent: DirIdx, name: &str) -> Result<DirIdx, String> {
+        if name.contains('/') || name == "." || name == ".." {
+            return Err(format!("invalid directory name {:?}", name));
+        }
+        if self.0[parent.0].files.iter().any(|(file, _)| file == name) {
+            return Err(format!(
+                "{:?} is listed as both a file and a directory",
+                name
+            ));
+        }
+        if let Some(&(_, idx)) = self.0[parent.0]
+            .child_dirs
+            .iter()
+            .find(|(child, _)| child == name)
+        {
+            return Ok(idx);
+        }
+        let idx = DirIdx(self.0.len());
+        self.0.push(Dir {
+            idx,
+            parent_dir: Some(parent),
+            child_dirs: Vec::new(),
+            files: Vec::new(),
+            size: 0,
+        });
+        self.0[parent.0].child_dirs.push((name.to_owned(), idx));
+        Ok(idx)
+    }
+
+    fn add_file(&mut self, dir: DirIdx, name: &str, size: usize) -> Result<(), String> {
+        let dir = &mut self.0[dir.0];
+        if dir.child_dirs.iter().any(|(child, _)| child == name) {
+            return Err(format!(
+                "{:?} is listed as both a file and a directory",
+                name
+            ));
+        }
+        match dir.files.iter_mut().find(|(file, _)| file == name) {
+            Some((_, old_size)) if *old_size != size => {
+                let message = format!("{:?} changed size from {} to {}", name, old_size, size);
+                *old_size = size;
+                Err(message)
+            }
+            Some(_) => Ok(()),
+            None => {
+                dir.files.push((name.to_owned(), size));
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CleanupConfig {
+    pub capacity: usize,
+    /// Free space needed once the clean-up is done.
+    pub needed: usize,
+    /// Absolute paths of files or directories that must survive.
+    pub protected: Vec<String>,
+}
+impl Default for CleanupConfig {
+    fn default() -> Self {
+        CleanupConfig {
+            capacity: 70000000,
+            needed: 30000000,
+            protected: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeletionPlan {
+    pub paths: Vec<String>,
+    pub freed: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CleanupError {
+    /// A protected path names nothing in the filesystem.
+    UnknownPath(String),
+    /// Everything that could free enough space is protected.
+    Impossible,
+}
+impl fmt::Display for CleanupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CleanupError::UnknownPath(path) => write!(f, "no such protected path {:?}", path),
+            CleanupError::Impossible => f.write_str("not enough unprotected space to free"),
+        }
+    }
+}
+impl std::error::Error for CleanupError {}
+
+/// A set of byte counts below a fixed limit, as bits.
+#[derive(Debug, Clone)]
+struct Sums {
+    words: Vec<u64>,
+    limit: usize,
+}
+impl Sums {
+    fn zero(limit: usize) -> Self {
+        let mut words = vec![0; limit.div_ceil(64)];
+        words[0] = 1;
+        Sums { words, limit }
+    }
+
+    fn contains(&self, sum: usize) -> bool {
+        sum < self.limit && self.words[sum / 64] & (1 << (sum % 64)) != 0
+    }
+
+    /// Add every sum in `other` plus `shift`.
+    fn insert_shifted(&mut self, other: &Sums, shift: usize) {
+        let (word_shift, bit_shift) = (shift / 64, shift % 64);
+        for idx in (word_shift..self.words.len()).rev() {
+            let src = idx - word_shift;
+            let mut word = other.words[src] << bit_shift;
+            if bit_shift > 0 && src > 0 {
+                word |= other.words[src - 1] >> (64 - bit_shift);
+            }
+            self.words[idx] |= word;
+        }
+        if !self.limit.is_multiple_of(64) {
+            *self.words.last_mut().unwrap() &= (1 << (self.limit % 64)) - 1;
+        }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(idx, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                let bit = word.trailing_zeros() as usize;
+                word &= word.wrapping_sub(1);
+                (bit < 64).then_some(idx * 64 + bit)
+            })
+        })
+    }
+}
+
+struct Planner<'a> {
+    fs: &'a Filesystem,
+    deletable: Vec<bool>,
+    limit: usize,
+}
+impl Planner<'_> {
+    /// `sums` combined with every amount that deleting within `dir` can free,
+    /// including nothing.
+    fn extend(&self, dir: &Dir, sums: &Sums) -> Sums {
+        let mut extended = sums.clone();
+        for (_, child) in &dir.child_dirs {
+            extended = self.extend(&self.fs.0[child.0], &extended);
+        }
+        if self.deletable[dir.idx.0] {
+            extended.insert_shifted(sums, dir.size);
+        }
+        extended
+    }
+
+    /// Find directories within `dir` that free exactly `target`, which must be
+    /// possible.
+    fn pick(&self, dir: &Dir, target: usize, picked: &mut Vec<DirIdx>) {
+        if target == 0 {
+            return;
+        }
+        if self.deletable[dir.idx.0] && dir.size == target {
+            picked.push(dir.idx);
+            return;
+        }
+        // `before[j]` is what the first `j` children can free together.
+        let mut before = vec![Sums::zero(self.limit)];
+        for (_, child) in &dir.child_dirs {
+            let next = self.extend(&self.fs.0[child.0], before.last().unwrap());
+            before.push(next);
+        }
+        let mut target = target;
+        for (j, (_, child)) in dir.child_dirs.iter().enumerate().rev() {
+            let child = &self.fs.0[child.0];
+            let child_target = self
+                .extend(child, &Sums::zero(self.limit))
+                .iter()
+                .find(|&freed| freed <= target && before[j].contains(target - freed))
+                .unwrap();
+            self.pick(child, child_target, picked);
+            target -= child_target;
+        }
+    }
+}
+
+impl Filesystem {
+    /// Pick directories, none inside another, that free enough space while
+    /// deleting as few bytes as possible. This is a knapsack over the tree,
+    /// so it takes time and memory proportional to the bytes to free.
+    pub fn plan_deletion(&self, config: &CleanupConfig) -> Result<DeletionPlan, CleanupError> {
+        let free = config.capacity.saturating_sub(self.total_size());
+        let required = config.needed.saturating_sub(free);
+
+        // A directory can't go if it holds anything protected or is inside a
+        // protected directory.
+        let mut deletable = vec![true; self.0.len()];
+        let mut inside_protected = vec![false; self.0.len()];
+        for path in &config.protected {
+            let dir = match self.dir_at(path) {
+                Some(dir) => {
+                    inside_protected[dir.idx.0] = true;
+                    dir
+                }
+                None => path
+                    .trim_end_matches('/')
+                    .rsplit_once('/')
+                    .and_then(|(parent, name)| {
+                        let parent = self.dir_at(parent)?;
+                        parent
+                            .files
+                            .iter()
+                            .any(|(file, _)| file == name)
+                            .then_some(parent)
+                    })
+                    .ok_or_else(|| CleanupError::UnknownPath(path.clone()))?,
+            };
+            let mut dir = Some(dir.idx);
+            while let Some(idx) = dir {
+                deletable[idx.0] = false;
+                dir = self.0[idx.0].parent_dir;
+            }
+        }
+        // Parents come before their children.
+        for idx in 1..self.0.len() {
+            let parent = self.0[idx].parent_dir.unwrap();
+            inside_protected[idx] |= inside_protected[parent.0];
+            deletable[idx] &= !inside_protected[idx];
+        }
+
+        // Nothing beats the most that can be freed, or the smallest single
+        // directory that is enough, so sums above those don't matter.
+        let mut most = vec![0; self.0.len()];
+        for idx in (0..self.0.len()).rev() {
+            let dir = &self.0[idx];
+            let children: usize = dir.child_dirs.iter().map(|(_, child)| most[child.0]).sum();
+            most[idx] = if deletable[idx] { dir.size } else { children };
+        }
+        if most[0] < required {
+            return Err(CleanupError::Impossible);
+        }
+        let bound = self
+            .dirs()
+            .filter(|dir| deletable[dir.idx.0] && dir.size >= required)
+            .map(Dir::size)
+            .min()
+            .unwrap_or(most[0])
+            .min(most[0]);
+
+        let planner = Planner {
+            fs: self,
+            deletable,
+            limit: bound + 1,
+        };
+        let root = &self.0[0];
+        let freed = planner
+            .extend(root, &Sums::zero(planner.limit))
+            .iter()
+            .find(|&freed| freed >= required)
+            .unwrap();
+        let mut picked = Vec::new();
+        planner.pick(root, freed, &mut picked);
+        let mut paths: Vec<_> = picked.iter().map(|idx| self.path(&self.0[idx.0])).collect();
+        paths.sort();
+        Ok(DeletionPlan { paths, freed })
+    }
+}
+
+pub(crate) fn validate(input: &str) -> Vec<Violation> {
+    Filesystem::reconstruct(input)
+        .1
+        .into_iter()
+        .map(|inconsistency| Violation::at_line(inconsistency.line - 1, inconsistency.message))
+        .collect()
+}
+
+impl Filesystem {
+    pub fn dirs(&self) -> std::slice::Iter<'_, Dir> {
+        self.0.iter()
+    }
+
+    pub fn total_size(&self) -> usize {
+        self.0[0].size
+    }
+
+    /// The absolute path of `dir`, ending in `/`.
+    pub fn path(&self, dir: &Dir) -> String {
+        let mut names = Vec::new();
+        let mut dir = dir;
+        while let Some(parent) = dir.parent_dir {
+            let parent = &self.0[parent.0];
+            let (name, _) = parent
+                .child_dirs
+                .iter()
+                .find(|(_, idx)| *idx == dir.idx)
+                .unwrap();
+            names.push(name.as_str());
+            dir = parent;
+        }
+        names
+            .iter()
+            .rev()
+            .fold("/".to_string(), |path, name| path + name + "/")
+    }
+
+    /// The directory at an absolute path.
+    pub fn dir_at(&self, path: &str) -> Option<&Dir> {
+        let mut dir = &self.0[0];
+        for name in path.split('/').filter(|name| !name.is_empty()) {
+            let (_, idx) = dir.child_dirs.iter().find(|(child, _)| child == name)?;
+            dir = &self.0[idx.0];
+        }
+        Some(dir)
+    }
+
+    /// Absolute paths of every directory called `name`.
+    pub fn paths_named(&self, name: &str) -> Vec<String> {
+        self.dirs()
+            .flat_map(|dir| &dir.child_dirs)
+            .filter(|(child, _)| child == name)
+            .map(|(_, idx)| self.path(&self.0[idx.0]))
+            .collect()
+    }
+
+    /// The `n` largest directories with their sizes, largest first.
+    pub fn largest_dirs(&self, n: usize) -> Vec<(String, usize)> {
+        self.dirs()
+            .map(|dir| (dir.size, Reverse(dir.idx.0)))
+            .top_k(n)
+            .into_vec()
+            .into_iter()
+            .map(|(size, Reverse(idx))| (self.path(&self.0[idx]), size))
+            .collect()
+    }
+
+    /// Every directory as `<size>\t<path>`, largest first, like `du | sort -rn`.
+    pub fn du(&self) -> String {
+        let mut dirs: Vec<_> = self.dirs().collect();
+        dirs.sort_by_key(|dir| (Reverse(dir.size), dir.idx.0));
+        dirs.iter().fold(String::new(), |mut out, dir| {
+            writeln!(out, "{}\t{}", dir.size, self.path(dir)).unwrap();
+            out
+        })
+    }
+
+    /// An indented listing in the style of the puzzle description, with
+    /// entries sorted by name.
+    pub fn tree(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "- / (dir, size={})", self.total_size()).unwrap();
+        self.write_tree(&self.0[0], 1, &mut out);
+        out
+    }
+
+    fn write_tree(&self, dir: &Dir, depth: usize, out: &mut String) {
+        let mut entries: Vec<(&str, Result<&Dir, usize>)> = dir
+            .child_dirs
+            .iter()
+            .map(|(name, idx)| (name.as_str(), Ok(&self.0[idx.0])))
+            .chain(
+                dir.files
+                    .iter()
+                    .map(|(name, size)| (name.as_str(), Err(*size))),
+            )
+            .collect();
+        entries.sort_by_key(|(name, _)| *name);
+        let indent = "  ".repeat(depth);
+        for (name, entry) in entries {
+            match entry {
+                Ok(child) => {
+                    writeln!(out, "{}- {} (dir, size={})", indent, name, child.size).unwrap();
+                    self.write_tree(child, depth + 1, out);
+                }
+                Err(size) => writeln!(out, "{}- {} (file, size={})", indent, name, size).unwrap(),
+            }
+        }
+    }
+}
+
+#[aoc(day7, part1)]
+pub fn part1(input: &str) -> usize {
+    let fs: Filesystem = input.parse().unwrap();
+    fs.dirs()
+        .map(Dir::size)
+        .filter(|&size| size <= 100000)
+        .sum()
+}
+
+#[aoc(day7, part2)]
+pub fn part2(input: &str) -> usize {
+    let fs: Filesystem = input.parse().unwrap();
+    let space_used = fs.total_size();
+    let unused_space = 70000000 - space_used;
+    let min_to_delete = 30000000 - unused_space;
+    fs.dirs()
+        .map(Dir::size)
+        .filter(|&size| size >= min_to_delete)
+        .min()
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messy_transcript() {
+        let transcript = "$ cd /a/b/../c\n$ ls\n10 x\n$ cd /\n$ ls\ndir a\n5 y\n$ cd a\n$ ls\ndir c\ndir c\n$ cd c\n$ ls\n12 x\n$ cd ../../..\n$ pwd";
+        let (fs, inconsistencies) = Filesystem::reconstruct(transcript);
+        assert_eq!(
+            inconsistencies,
+            vec![
+                Inconsistency {
+                    line: 14,
+                    message: "\"x\" changed size from 10 to 12".to_string(),
+                },
+                Inconsistency {
+                    line: 16,
+                    message: "unknown command \"pwd\"".to_string(),
+                },
+            ]
+        );
+        // /, /a, /a/b and /a/c.
+        assert_eq!(fs.dirs().count(), 4);
+        assert_eq!(fs.total_size(), 17);
+        assert_eq!(
+            validate(transcript),
+            vec![
+                Violation::at_line(13, "\"x\" changed size from 10 to 12"),
+                Violation::at_line(15, "unknown command \"pwd\""),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports() {
+        let transcript = "$ cd /\n$ ls\ndir a\n14848514 b.txt\n8504156 c.dat\ndir d\n$ cd a\n$ ls\ndir e\n29116 f\n2557 g\n62596 h.lst\n$ cd e\n$ ls\n584 i\n$ cd ..\n$ cd ..\n$ cd d\n$ ls\n4060174 j\n8033020 d.log\n5626152 d.ext\n7214296 k";
+        let fs: Filesystem = transcript.parse().unwrap();
+        assert_eq!(fs.dir_at("/a/e").unwrap().size(), 584);
+        assert_eq!(fs.paths_named("e"), vec!["/a/e/".to_string()]);
+        assert_eq!(
+            fs.largest_dirs(2),
+            vec![("/".to_string(), 48381165), ("/d/".to_string(), 24933642)]
+        );
+        assert_eq!(
+            fs.du(),
+            "48381165\t/\n24933642\t/d/\n94853\t/a/\n584\t/a/e/\n"
+        );
+        assert_eq!(
+            fs.plan_deletion(&CleanupConfig::default()),
+            Ok(DeletionPlan {
+                paths: vec!["/d/".to_string()],
+                freed: 24933642,
+            })
+        );
+        let mut config = CleanupConfig {
+            capacity: 48381165,
+            needed: 24933642 + 100,
+            protected: vec!["/a/h.lst".to_string()],
+        };
+        assert_eq!(
+            fs.plan_deletion(&config),
+            Ok(DeletionPlan {
+                paths: vec!["/a/e/".to_string(), "/d/".to_string()],
+                freed: 24933642 + 584,
+            })
+        );
+        config.protected.push("/a/e/".to_string());
+        assert_eq!(fs.plan_deletion(&config), Err(CleanupError::Impossible));
+        config.protected.push("/kep/".to_string());
+        assert_eq!(
+            fs.plan_deletion(&config),
+            Err(CleanupError::UnknownPath("/kep/".to_string()))
+        );
+        assert_eq!(
+            fs.tree().lines().take(4).collect::<Vec<_>>(),
+            vec![
+                "- / (dir, size=48381165)",
+                "  - a (dir, size=94853)",
+                "    - e (dir, size=584)",
+                "      - i (file, size=584)",
+            ]
+        );
+
+        // Protecting a directory protects everything under it.
+        let fs: Filesystem =
+            "$ cd /\n$ ls\ndir keep\n1 x\n$ cd keep\n$ ls\ndir sub\n5 z\n$ cd sub\n$ ls\n100 y"
+                .parse()
+                .unwrap();
+        let mut config = CleanupConfig {
+            capacity: 106,
+            needed: 100,
+            protected: vec!["/keep/".to_string()],
+        };
+        assert_eq!(fs.plan_deletion(&config), Err(CleanupError::Impossible));
+        config.protected = vec!["/keep/sub/y".to_string()];
+        assert_eq!(fs.plan_deletion(&config), Err(CleanupError::Impossible));
+        config.protected.clear();
+        assert_eq!(
+            fs.plan_deletion(&config),
+            Ok(DeletionPlan {
+                paths: vec!["/keep/sub/".to_string()],
+                freed: 100,
+            })
+        );
+    }
+}
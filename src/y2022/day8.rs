@@ -0,0 +1,385 @@
+use std::{fmt::Write, str::FromStr};
+
+use aoc_runner_derive::aoc;
+
+pub struct TreeMap {
+    tree_heights: Vec<u32>,
+    num_cols: usize,
+}
+impl TreeMap {
+    pub fn num_rows(&self) -> usize {
+        self.tree_heights.len() / self.num_cols
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    fn trees_above(&self, idx: usize) -> impl Iterator<Item = u32> + '_ {
+        let tree_row = idx / self.num_cols;
+        let tree_col = idx % self.num_cols;
+        (0..tree_row)
+            .rev()
+            .map(move |ri| self.tree_heights[ri * self.num_cols + tree_col])
+    }
+    fn trees_below(&self, idx: usize) -> impl Iterator<Item = u32> + '_ {
+        let num_rows = self.tree_heights.len() / self.num_cols;
+        let tree_row = idx / self.num_cols;
+        let tree_col = idx % self.num_cols;
+        ((tree_row + 1).min(num_rows)..num_rows)
+            .map(move |ri| self.tree_heights[ri * self.num_cols + tree_col])
+    }
+    fn trees_left(&self, idx: usize) -> impl Iterator<Item = u32> + '_ {
+        let tree_row = idx / self.num_cols;
+        let tree_col = idx % self.num_cols;
+        (0..tree_col)
+            .rev()
+            .map(move |ci| self.tree_heights[tree_row * self.num_cols + ci])
+    }
+    fn trees_right(&self, idx: usize) -> impl Iterator<Item = u32> + '_ {
+        let tree_row = idx / self.num_cols;
+        let tree_col = idx % self.num_cols;
+        ((tree_col + 1).min(self.num_cols)..self.num_cols)
+            .map(move |ci| self.tree_heights[tree_row * self.num_cols + ci])
+    }
+}
+/// Each row is either a run of single digits, as in the puzzle, or heights of
+/// any size separated by commas or whitespace.
+impl FromStr for TreeMap {
+    type Err = &'static str;
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut tree_heights = Vec::new();
+        let mut num_cols = None;
+        for line in input.lines().map(str::trim) {
+            let row_start = tree_heights.len();
+            let is_separator = |c: char| c == ',' || c.is_whitespace();
+            if line.contains(is_separator) {
+                for height in line.split(is_separator).filter(|h| !h.is_empty()) {
+                    tree_heights.push(height.parse().map_err(|_| "invalid height")?);
+                }
+            } else {
+                for b in line.bytes() {
+                    if !b.is_ascii_digit() {
+                        return Err("invalid height");
+                    }
+                    tree_heights.push((b - b'0') as u32);
+                }
+            }
+            let row_len = tree_heights.len() - row_start;
+            if *num_cols.get_or_insert(row_len) != row_len {
+                return Err("rows have different lengths");
+            }
+        }
+        match num_cols {
+            Some(num_cols) if num_cols > 0 => Ok(TreeMap {
+                tree_heights,
+                num_cols,
+            }),
+            _ => Err("empty map"),
+        }
+    }
+}
+
+pub const CARDINAL_DIRECTIONS: &[(isize, isize)] = &[(-1, 0), (1, 0), (0, -1), (0, 1)];
+pub const ALL_DIRECTIONS: &[(isize, isize)] = &[
+    (-1, 0),
+    (1, 0),
+    (0, -1),
+    (0, 1),
+    (-1, -1),
+    (-1, 1),
+    (1, -1),
+    (1, 1),
+];
+
+impl TreeMap {
+    /// Whether each tree, in row-major order, can be seen from outside the
+    /// grid.
+    pub fn visibility(&self) -> Vec<bool> {
+        self.visibility_from_edges(CARDINAL_DIRECTIONS)
+    }
+
+    /// The scenic score of each tree, in row-major order.
+    pub fn scenic_scores(&self) -> Vec<usize> {
+        self.scenic_scores_with(CARDINAL_DIRECTIONS, 0)
+    }
+
+    /// Whether each tree can be seen from outside the grid looking back along
+    /// any of `directions`.
+    pub fn visibility_from_edges(&self, directions: &[(isize, isize)]) -> Vec<bool> {
+        self.sweep_all(directions, 0).0
+    }
+
+    /// Scenic scores looking along `directions` from `viewer_offset` above
+    /// the top of each tree.
+    pub fn scenic_scores_with(
+        &self,
+        directions: &[(isize, isize)],
+        viewer_offset: u32,
+    ) -> Vec<usize> {
+        self.sweep_all(directions, viewer_offset).1
+    }
+
+    fn pos(&self, idx: usize) -> (isize, isize) {
+        (
+            (idx / self.num_cols) as isize,
+            (idx % self.num_cols) as isize,
+        )
+    }
+
+    fn idx(&self, (row, col): (isize, isize)) -> Option<usize> {
+        let in_bounds = (0..self.num_rows() as isize).contains(&row)
+            && (0..self.num_cols as isize).contains(&col);
+        in_bounds.then(|| row as usize * self.num_cols + col as usize)
+    }
+
+    /// The trees from `idx` (exclusive) to the edge heading in `direction`.
+    fn ray(&self, idx: usize, (dr, dc): (isize, isize)) -> impl Iterator<Item = usize> + '_ {
+        let (row, col) = self.pos(idx);
+        (1..).map_while(move |step| self.idx((row + dr * step, col + dc * step)))
+    }
+
+    /// Sweep every line in each direction, O(1) amortized per tree per
+    /// direction when `viewer_offset` is 0 and O(log n) otherwise.
+    fn sweep_all(
+        &self,
+        directions: &[(isize, isize)],
+        viewer_offset: u32,
+    ) -> (Vec<bool>, Vec<usize>) {
+        let mut visible = vec![false; self.tree_heights.len()];
+        let mut scores = vec![1; self.tree_heights.len()];
+        let mut stack = Vec::new();
+        for &(dr, dc) in directions {
+            // To look towards the edge in `direction`, each line starts at
+            // that edge and runs backwards.
+            let starts = (0..self.tree_heights.len()).filter(|&idx| {
+                let (row, col) = self.pos(idx);
+                self.idx((row + dr, col + dc)).is_none()
+            });
+            for start in starts {
+                let line = std::iter::once(start).chain(self.ray(start, (-dr, -dc)));
+                self.sweep(line, viewer_offset, &mut visible, &mut scores, &mut stack);
+            }
+        }
+        (visible, scores)
+    }
+
+    /// Look back along `line` from each tree. `stack` holds the trees seen so
+    /// far that nothing since is at least as tall as, so heights on it never
+    /// increase and the nearest tree that blocks a view is the last one at
+    /// least as tall as the viewer.
+    fn sweep(
+        &self,
+        line: impl Iterator<Item = usize>,
+        viewer_offset: u32,
+        visible: &mut [bool],
+        scores: &mut [usize],
+        stack: &mut Vec<(u32, usize)>,
+    ) {
+        stack.clear();
+        for (pos, idx) in line.enumerate() {
+            let height = self.tree_heights[idx];
+            while stack.last().is_some_and(|&(blocker, _)| blocker < height) {
+                stack.pop();
+            }
+            if stack.is_empty() {
+                visible[idx] = true;
+            }
+            let eye = height.saturating_add(viewer_offset);
+            let num_blockers = if eye == height {
+                stack.len()
+            } else {
+                stack.partition_point(|&(blocker, _)| blocker >= eye)
+            };
+            scores[idx] *= match num_blockers.checked_sub(1) {
+                Some(nearest) => pos - stack[nearest].1,
+                None => pos,
+            };
+            stack.push((height, pos));
+        }
+    }
+
+    /// Which trees someone standing on the tree at `observer`, with their eyes
+    /// `viewer_offset` above it, can see along `directions`. The view along
+    /// each line ends at the first tree at least as tall as their eyes.
+    /// Returns `None` if `observer` is off the map.
+    pub fn visible_from(
+        &self,
+        (row, col): (usize, usize),
+        directions: &[(isize, isize)],
+        viewer_offset: u32,
+    ) -> Option<Vec<bool>> {
+        let observer = self.idx((row.try_into().ok()?, col.try_into().ok()?))?;
+        let eye = self.tree_heights[observer].saturating_add(viewer_offset);
+        let mut visible = vec![false; self.tree_heights.len()];
+        visible[observer] = true;
+        for &direction in directions {
+            for idx in self.ray(observer, direction) {
+                visible[idx] = true;
+                if self.tree_heights[idx] >= eye {
+                    break;
+                }
+            }
+        }
+        Some(visible)
+    }
+
+    /// Visibility of one tree by walking all four rays.
+    pub fn visible_by_rays(&self, idx: usize) -> bool {
+        let height = self.tree_heights[idx];
+        self.trees_above(idx)
+            .all(|neighbor_height| height > neighbor_height)
+            || self
+                .trees_below(idx)
+                .all(|neighbor_height| height > neighbor_height)
+            || self
+                .trees_left(idx)
+                .all(|neighbor_height| height > neighbor_height)
+            || self
+                .trees_right(idx)
+                .all(|neighbor_height| height > neighbor_height)
+    }
+
+    /// Scenic score of one tree by walking all four rays.
+    pub fn scenic_score_by_rays(&self, idx: usize) -> usize {
+        let height = self.tree_heights[idx];
+        // we want an iterator that yields elements until it encounters a tree
+        // taller than height, but it should include the first taller tree encountered.
+        // take_while will be off by one because it wouldn't count the first taller tree.
+        let yield_until_blocked = |blocked: &mut bool, neighbor_height| {
+            if *blocked {
+                return None;
+            }
+            *blocked = neighbor_height >= height;
+            Some(neighbor_height)
+        };
+
+        self.trees_above(idx)
+            .scan(false, yield_until_blocked)
+            .count()
+            * self
+                .trees_below(idx)
+                .scan(false, yield_until_blocked)
+                .count()
+            * self
+                .trees_left(idx)
+                .scan(false, yield_until_blocked)
+                .count()
+            * self
+                .trees_right(idx)
+                .scan(false, yield_until_blocked)
+                .count()
+    }
+}
+
+/// An ASCII (P2) PGM image of `values` laid out `num_cols` wide, brightest
+/// where largest. Values are scaled down if they don't fit in 16 bits.
+///
+/// Panics unless `values` fills whole rows of `num_cols`, with `num_cols > 0`.
+pub fn to_pgm(values: &[usize], num_cols: usize) -> String {
+    assert!(
+        num_cols > 0 && values.len().is_multiple_of(num_cols),
+        "can't lay out {} values in rows of {}",
+        values.len(),
+        num_cols
+    );
+    let max = values.iter().copied().max().unwrap_or(0).max(1);
+    let scale = |value: usize| (value as u128 * max.min(65535) as u128 / max as u128) as usize;
+    let mut pgm = format!(
+        "P2\n{} {}\n{}\n",
+        num_cols,
+        values.len() / num_cols,
+        max.min(65535)
+    );
+    for row in values.chunks(num_cols) {
+        let row: Vec<_> = row.iter().map(|&value| scale(value).to_string()).collect();
+        writeln!(pgm, "{}", row.join(" ")).unwrap();
+    }
+    pgm
+}
+
+#[aoc(day8, part1)]
+pub fn part1(input: &str) -> usize {
+    let trees: TreeMap = input.parse().unwrap();
+    trees
+        .visibility()
+        .into_iter()
+        .filter(|&visible| visible)
+        .count()
+}
+
+#[aoc(day8, part2)]
+pub fn part2(input: &str) -> usize {
+    let trees: TreeMap = input.parse().unwrap();
+    trees.scenic_scores().into_iter().max().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn sweeps_match_rays(num_cols in 1usize..12, heights in prop::collection::vec(0u32..10, 1..144)) {
+            let num_rows = heights.len().div_ceil(num_cols);
+            let mut tree_heights = heights;
+            tree_heights.resize(num_rows * num_cols, 0);
+            let trees = TreeMap { tree_heights, num_cols };
+            let idxs = 0..trees.tree_heights.len();
+            prop_assert_eq!(
+                trees.visibility(),
+                idxs.clone().map(|idx| trees.visible_by_rays(idx)).collect::<Vec<_>>()
+            );
+            prop_assert_eq!(
+                trees.scenic_scores(),
+                idxs.map(|idx| trees.scenic_score_by_rays(idx)).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn analytics() {
+        let trees: TreeMap = "30373\n25512\n65332\n33549\n35390".parse().unwrap();
+        let wide: TreeMap = "3,0,3,7,3\n2 5 5 1 2\n6,5,3,3,2\n3 3 5 4 9\n3,5,3,9,0"
+            .parse()
+            .unwrap();
+        assert_eq!(wide.tree_heights, trees.tree_heights);
+        assert_eq!(
+            "10,2\n3".parse::<TreeMap>().err(),
+            Some("rows have different lengths")
+        );
+
+        let scores = trees.scenic_scores_with(ALL_DIRECTIONS, 0);
+        // The 5 in the middle of the fourth row sees 1 up-left, 1 down-left,
+        // 2 up-right and 1 down-right on top of its cardinal score of 8.
+        assert_eq!(scores[3 * 5 + 2], 8 * 2);
+        let taller = trees.scenic_scores_with(CARDINAL_DIRECTIONS, 10);
+        // Nothing blocks the view: 3 up, 1 down, 2 left and 2 right.
+        assert_eq!(taller[3 * 5 + 2], 12);
+        assert_eq!(
+            trees
+                .visibility_from_edges(ALL_DIRECTIONS)
+                .iter()
+                .filter(|&&v| v)
+                .count(),
+            trees.visibility().iter().filter(|&&v| v).count() + 1
+        );
+
+        let seen = trees.visible_from((3, 2), CARDINAL_DIRECTIONS, 0).unwrap();
+        assert_eq!(seen.iter().filter(|&&v| v).count(), 1 + 7);
+        assert_eq!(trees.visible_from((5, 5), CARDINAL_DIRECTIONS, 0), None);
+        assert_eq!(
+            trees.visible_from((0, usize::MAX), CARDINAL_DIRECTIONS, 0),
+            None
+        );
+        let pgm = to_pgm(&trees.scenic_scores(), trees.num_cols());
+        assert!(pgm.starts_with("P2\n5 5\n8\n0 0 0 0 0\n0 1 4 1 0\n"));
+    }
+
+    #[test]
+    #[should_panic(expected = "can't lay out 3 values in rows of 0")]
+    fn pgm_without_columns() {
+        to_pgm(&[1, 2, 3], 0);
+    }
+}
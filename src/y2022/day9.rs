@@ -0,0 +1,374 @@
+use std::{collections::HashSet, convert::Infallible, str::FromStr};
+
+use aoc_runner_derive::aoc;
+
+use crate::bitset::RadixBitSet;
+
+#[derive(Debug, Default, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Pos {
+    x: isize,
+    y: isize,
+}
+impl Pos {
+    pub fn x(self) -> isize {
+        self.x
+    }
+
+    pub fn y(self) -> isize {
+        self.y
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+impl Direction {
+    fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, 1),
+            Direction::Down => (0, -1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+            Direction::UpLeft => (-1, 1),
+            Direction::UpRight => (1, 1),
+            Direction::DownLeft => (-1, -1),
+            Direction::DownRight => (1, -1),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Motion {
+    num_steps: usize,
+    direction: Direction,
+}
+
+impl FromStr for Motion {
+    type Err = Infallible;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let (direction, num_steps) = input.split_once(' ').unwrap();
+        let direction = match direction {
+            "U" => Direction::Up,
+            "D" => Direction::Down,
+            "L" => Direction::Left,
+            "R" => Direction::Right,
+            "UL" => Direction::UpLeft,
+            "UR" => Direction::UpRight,
+            "DL" => Direction::DownLeft,
+            "DR" => Direction::DownRight,
+            _ => panic!("uh oh"),
+        };
+        let num_steps: usize = num_steps.parse().unwrap();
+        Ok(Motion {
+            num_steps,
+            direction,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Rope {
+    knots: Vec<Pos>,
+    visited: Vec<HashSet<Pos>>,   // per knot, including the start
+    trace: Option<Vec<Vec<Pos>>>, // knot positions after each step
+}
+
+impl Rope {
+    pub fn new(num_knots: usize) -> Self {
+        assert!(num_knots > 0, "uh oh");
+        Rope {
+            knots: vec![Pos::default(); num_knots],
+            visited: vec![HashSet::from([Pos::default()]); num_knots],
+            trace: None,
+        }
+    }
+
+    /// A rope that also records where every knot is after every step.
+    pub fn with_trace(num_knots: usize) -> Self {
+        Rope {
+            trace: Some(vec![vec![Pos::default(); num_knots]]),
+            ..Rope::new(num_knots)
+        }
+    }
+
+    pub fn walk(&mut self, motion: Motion) {
+        for _ in 0..motion.num_steps {
+            self.move_head(motion.direction);
+            for i in 1..self.knots.len() {
+                Self::adjust_tail(self.knots[i - 1], &mut self.knots[i]);
+            }
+            for (visited, &knot) in self.visited.iter_mut().zip(&self.knots) {
+                visited.insert(knot);
+            }
+            if let Some(trace) = &mut self.trace {
+                trace.push(self.knots.clone());
+            }
+        }
+    }
+
+    fn move_head(&mut self, direction: Direction) {
+        let (dx, dy) = direction.delta();
+        self.knots[0].x += dx;
+        self.knots[0].y += dy;
+    }
+
+    fn adjust_tail(head: Pos, tail: &mut Pos) {
+        let x_diff = head.x - tail.x;
+        let y_diff = head.y - tail.y;
+        if x_diff.abs() > 1 || y_diff.abs() > 1 {
+            tail.x += x_diff.signum();
+            tail.y += y_diff.signum();
+        }
+    }
+
+    pub fn visited(&self, knot: usize) -> &HashSet<Pos> {
+        &self.visited[knot]
+    }
+
+    /// Number of recorded steps, or `None` without a trace.
+    pub fn trace_len(&self) -> Option<usize> {
+        self.trace.as_ref().map(|trace| trace.len() - 1)
+    }
+
+    fn bounds(&self) -> (Pos, Pos) {
+        let all = self.visited.iter().flatten();
+        let min = Pos {
+            x: all.clone().map(|pos| pos.x).min().unwrap(),
+            y: all.clone().map(|pos| pos.y).min().unwrap(),
+        };
+        let max = Pos {
+            x: all.clone().map(|pos| pos.x).max().unwrap(),
+            y: all.map(|pos| pos.y).max().unwrap(),
+        };
+        (min, max)
+    }
+
+    fn render(&self, cell: impl Fn(Pos) -> char) -> String {
+        let (min, max) = self.bounds();
+        let mut grid = String::new();
+        for y in (min.y..=max.y).rev() {
+            grid.extend((min.x..=max.x).map(|x| cell(Pos { x, y })));
+            grid.push('\n');
+        }
+        grid
+    }
+
+    /// The knots after `step` steps in the puzzle's style: `H` for the head,
+    /// then `T` or knot numbers, with earlier knots on top and `s` for the
+    /// start. Needs a rope made with `with_trace`.
+    pub fn render_step(&self, step: usize) -> String {
+        let knots = &self.trace.as_ref().expect("no trace recorded")[step];
+        let label = |idx: usize| match idx {
+            0 => 'H',
+            _ if knots.len() == 2 => 'T',
+            _ => char::from_digit(idx as u32 % 36, 36).unwrap(),
+        };
+        self.render(|pos| match knots.iter().position(|&knot| knot == pos) {
+            Some(idx) => label(idx),
+            None if pos == Pos::default() => 's',
+            None => '.',
+        })
+    }
+
+    /// Every position `knot` has visited as `#`, with `s` for the start.
+    pub fn render_visited(&self, knot: usize) -> String {
+        self.render(|pos| match pos {
+            _ if pos == Pos::default() => 's',
+            _ if self.visited[knot].contains(&pos) => '#',
+            _ => '.',
+        })
+    }
+}
+
+/// Positions visited by the tail. The head's bounding box also bounds every
+/// other knot, so if it fits in 16 bits a side, cells are packed into a
+/// `RadixBitSet` key relative to its corner.
+enum Visited {
+    Packed { min: Pos, cells: RadixBitSet },
+    Sparse(HashSet<Pos>),
+}
+impl Visited {
+    fn new(motions: &[Motion]) -> Self {
+        let (mut min, mut max, mut head) = (Pos::default(), Pos::default(), Pos::default());
+        for motion in motions {
+            let (dx, dy) = motion.direction.delta();
+            head.x += dx * motion.num_steps as isize;
+            head.y += dy * motion.num_steps as isize;
+            min = Pos {
+                x: min.x.min(head.x),
+                y: min.y.min(head.y),
+            };
+            max = Pos {
+                x: max.x.max(head.x),
+                y: max.y.max(head.y),
+            };
+        }
+        if max.x - min.x < 1 << 16 && max.y - min.y < 1 << 16 {
+            Visited::Packed {
+                min,
+                cells: RadixBitSet::new(),
+            }
+        } else {
+            Visited::Sparse(HashSet::new())
+        }
+    }
+
+    fn insert(&mut self, pos: Pos) {
+        match self {
+            Visited::Packed { min, cells } => {
+                cells.set_bit((pos.x - min.x) as u32 | ((pos.y - min.y) as u32) << 16);
+            }
+            Visited::Sparse(cells) => {
+                cells.insert(pos);
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Visited::Packed { cells, .. } => cells.len() as usize,
+            Visited::Sparse(cells) => cells.len(),
+        }
+    }
+}
+
+/// Same answer as walking a `Rope`, but only tracks the tail. While the head
+/// stays within reach of the next knot nothing else moves, so the head jumps
+/// straight to the last such cell. Otherwise each step stops propagating at
+/// the first knot that doesn't move, since none behind it can move either,
+/// and the tail is only recorded when it moves.
+pub fn count_tail_visits(motions: &[Motion], num_knots: usize) -> usize {
+    assert!(num_knots > 0, "uh oh");
+    let mut knots = vec![Pos::default(); num_knots];
+    let mut visited = Visited::new(motions);
+    visited.insert(Pos::default());
+    let tail = num_knots - 1;
+    for motion in motions {
+        let (dx, dy) = motion.direction.delta();
+        if tail == 0 {
+            for _ in 0..motion.num_steps {
+                knots[0].x += dx;
+                knots[0].y += dy;
+                visited.insert(knots[0]);
+            }
+            continue;
+        }
+        let mut remaining = motion.num_steps;
+        while remaining > 0 {
+            // Steps along an axis before the head is more than one cell past
+            // knot 1 on it.
+            let reach = |head: isize, knot: isize, d: isize| match d {
+                0 => usize::MAX,
+                _ => (1 - (head - knot) * d) as usize,
+            };
+            let free = reach(knots[0].x, knots[1].x, dx)
+                .min(reach(knots[0].y, knots[1].y, dy))
+                .min(remaining);
+            knots[0].x += dx * free as isize;
+            knots[0].y += dy * free as isize;
+            remaining -= free;
+            if remaining == 0 {
+                break;
+            }
+
+            knots[0].x += dx;
+            knots[0].y += dy;
+            remaining -= 1;
+            for i in 1..num_knots {
+                let x_diff = knots[i - 1].x - knots[i].x;
+                let y_diff = knots[i - 1].y - knots[i].y;
+                if x_diff.abs() <= 1 && y_diff.abs() <= 1 {
+                    break;
+                }
+                knots[i].x += x_diff.signum();
+                knots[i].y += y_diff.signum();
+                if i == tail {
+                    visited.insert(knots[i]);
+                }
+            }
+        }
+    }
+    visited.len()
+}
+
+fn parse_motions(input: &str) -> Vec<Motion> {
+    input
+        .lines()
+        .map(|motion| motion.parse().unwrap())
+        .collect()
+}
+
+#[aoc(day9, part1)]
+pub fn part1(input: &str) -> usize {
+    count_tail_visits(&parse_motions(input), 2)
+}
+
+#[aoc(day9, part2)]
+pub fn part2(input: &str) -> usize {
+    count_tail_visits(&parse_motions(input), 10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn motion() -> impl Strategy<Value = Motion> {
+        (0usize..8, 0usize..20).prop_map(|(direction, num_steps)| {
+            let direction = ["U", "D", "L", "R", "UL", "UR", "DL", "DR"][direction];
+            format!("{direction} {num_steps}").parse().unwrap()
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn fast_matches_rope(motions in prop::collection::vec(motion(), 0..40), num_knots in 1usize..12) {
+            let mut rope = Rope::new(num_knots);
+            for &motion in &motions {
+                rope.walk(motion);
+            }
+            prop_assert_eq!(count_tail_visits(&motions, num_knots), rope.visited(num_knots - 1).len());
+        }
+    }
+
+    #[test]
+    fn far_walks_fall_back_to_sparse() {
+        let motions = parse_motions("R 70000\nL 70000");
+        assert_eq!(count_tail_visits(&motions, 10), 69992);
+    }
+    #[test]
+    fn trace() {
+        let mut rope = Rope::with_trace(10);
+        for motion in "R 5\nU 8".lines() {
+            rope.walk(motion.parse().unwrap());
+        }
+        assert_eq!(rope.trace_len(), Some(13));
+        assert_eq!(
+            rope.render_step(13),
+            ".....H\n.....1\n.....2\n.....3\n....54\n...6..\n..7...\n.8....\n9.....\n"
+        );
+        assert_eq!(rope.visited(9).len(), 1);
+        let mut head: Vec<_> = rope
+            .visited(0)
+            .iter()
+            .map(|pos| (pos.x(), pos.y()))
+            .collect();
+        head.sort_unstable();
+        assert_eq!(head[..2], [(0, 0), (1, 0)]);
+        assert_eq!(head[13], (5, 8));
+        assert_eq!(rope.visited(0).len(), 14);
+
+        let mut diagonal = Rope::new(2);
+        diagonal.walk("UR 12".parse().unwrap());
+        assert_eq!(diagonal.visited(1).len(), 12);
+    }
+}
@@ -0,0 +1,74 @@
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+// Run under node with wasm-bindgen-test-runner as the target runner:
+// CARGO_TARGET_WASM32_UNKNOWN_UNKNOWN_RUNNER=wasm-bindgen-test-runner \
+//   cargo test --target wasm32-unknown-unknown --no-default-features --features wasm \
+//     --test wasm
+
+use aoc_2022::wasm::solve;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const DAY1_EXAMPLE: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+
+#[wasm_bindgen_test]
+fn solves_day1() {
+    assert_eq!(solve(1, 1, DAY1_EXAMPLE), "24000");
+    assert_eq!(solve(1, 2, DAY1_EXAMPLE), "45000");
+}
+
+#[wasm_bindgen_test]
+fn solves_day2() {
+    assert_eq!(solve(2, 1, "A Y\nB X\nC Z"), "15");
+    assert_eq!(solve(2, 2, "A Y\nB X\nC Z"), "12");
+}
+
+const DAY11_EXAMPLE: &str = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1
+";
+
+#[wasm_bindgen_test]
+fn solves_day11_beyond_32_bits() {
+    assert_eq!(solve(11, 2, DAY11_EXAMPLE), "2713310158");
+}
+
+#[wasm_bindgen_test]
+fn solves_day15_beyond_32_bits() {
+    // A single sensor that covers everything but the far corner of the search area.
+    let input = "Sensor at x=0, y=0: closest beacon is at x=3999999, y=4000000";
+    assert_eq!(solve(15, 2, input), "16000004000000");
+}
+
+#[wasm_bindgen_test]
+fn solves_day20_beyond_32_bits() {
+    assert_eq!(solve(20, 2, "1\n2\n-3\n3\n-2\n0\n4"), "1623178306");
+}
+
+#[wasm_bindgen_test]
+fn solves_day21_beyond_32_bits() {
+    let input = "root: aaaa * bbbb\naaaa: 5000000\nbbbb: 3000000\nhumn: 1";
+    assert_eq!(solve(21, 1, input), "15000000000000");
+}